@@ -0,0 +1,423 @@
+use clap::{Args, Parser, Subcommand};
+use std::error::Error;
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use ffxii_tza_rng::character::Character;
+use ffxii_tza_rng::enemy::Enemy;
+use ffxii_tza_rng::index::SeedIndex;
+use ffxii_tza_rng::rng_helper::RNGHelper;
+use ffxii_tza_rng::search::SearchJob;
+use ffxii_tza_rng::session::Session;
+use ffxii_tza_rng::shard::{self, Coordinator, ShardJob};
+use ffxii_tza_rng::spell::Spell;
+use ffxii_tza_rng::version::GameVersion;
+
+mod output;
+mod tui;
+
+use output::Format;
+
+/// Find and step through the RNG in Final Fantasy XII
+#[derive(Parser, Debug)]
+#[command(name = "ffxii-rng", version)]
+struct Cli {
+    #[command(flatten)]
+    character: CharacterArgs,
+    /// How to print results
+    #[arg(long, value_enum, default_value = "plain", global = true)]
+    format: Format,
+    /// Pick up the character, enemy, seed and position from this session file, and save them back to it
+    #[arg(long, global = true)]
+    session: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// The character casting the heals
+#[derive(Args, Debug)]
+struct CharacterArgs {
+    #[arg(long, default_value_t = 70, global = true)]
+    level: u8,
+    #[arg(long, default_value_t = 99, global = true)]
+    magic: u8,
+    #[arg(long, default_value = "Cure", value_parser = parse_spell, global = true)]
+    spell: Spell,
+    #[arg(long, global = true)]
+    serenity: bool,
+    #[arg(long, global = true)]
+    faith: bool,
+    /// Vanilla, IZJS, "TZA Console" or "TZA PC"
+    #[arg(long, default_value = "TZA Console", value_parser = parse_version, global = true)]
+    game: GameVersion,
+}
+
+impl CharacterArgs {
+    fn character(&self) -> Character {
+        let mut character = Character::new(self.level, self.magic, self.spell, self.serenity);
+        character.faith = self.faith;
+        character.version = self.game;
+        character
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Find the seed which gives a run of heals
+    FindSeed {
+        /// Heals in the order they were cast
+        #[arg(required = true, allow_negative_numbers = true)]
+        values: Vec<i32>,
+        #[arg(long, default_value_t = 1)]
+        min: u32,
+        #[arg(long, default_value_t = u32::MAX)]
+        max: u32,
+        /// How far into each seed to look
+        #[arg(long, default_value_t = 1_000)]
+        iters: usize,
+        /// Save progress here, resuming from it if it already exists
+        #[arg(long)]
+        checkpoint: Option<PathBuf>,
+        /// Look the heals up in a prebuilt seed index instead of searching
+        #[arg(long, conflicts_with = "checkpoint")]
+        index: Option<PathBuf>,
+    },
+    /// Find where a run of heals next comes up in a known seed
+    FindNext {
+        #[arg(required = true, allow_negative_numbers = true)]
+        values: Vec<i32>,
+        #[arg(long)]
+        seed: Option<u32>,
+        /// Values already used
+        #[arg(long)]
+        position: Option<u32>,
+        #[arg(long, default_value_t = 100_000)]
+        limit: usize,
+    },
+    /// Print the upcoming heals and chest rolls
+    Table {
+        #[arg(long)]
+        seed: Option<u32>,
+        #[arg(long)]
+        position: Option<u32>,
+        #[arg(long, default_value_t = 20)]
+        count: usize,
+    },
+    /// Find the next positions where a chest with the given spawn chance appears
+    Chest {
+        #[arg(long)]
+        seed: Option<u32>,
+        #[arg(long)]
+        position: Option<u32>,
+        /// Spawn chance, out of 100
+        #[arg(long)]
+        chance: u8,
+        #[arg(long, default_value_t = 5)]
+        count: usize,
+        /// How far ahead to look
+        #[arg(long, default_value_t = 1_000)]
+        limit: u32,
+    },
+    /// Split a seed search between worker processes, on this machine or others
+    Shard {
+        #[command(subcommand)]
+        command: ShardCommand,
+    },
+    /// Work interactively in the terminal
+    Tui {
+        #[arg(long, default_value_t = 1)]
+        min: u32,
+        #[arg(long, default_value_t = u32::MAX)]
+        max: u32,
+        #[arg(long, default_value_t = 1_000)]
+        iters: usize,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum ShardCommand {
+    /// Hand the search out to workers and print the seed they find
+    Serve {
+        #[arg(required = true, allow_negative_numbers = true)]
+        values: Vec<i32>,
+        /// Address workers connect to
+        #[arg(long, default_value = "0.0.0.0:7878")]
+        bind: String,
+        #[arg(long, default_value_t = 1)]
+        min: u32,
+        #[arg(long, default_value_t = u32::MAX)]
+        max: u32,
+        #[arg(long, default_value_t = 1_000)]
+        iters: usize,
+        /// Seeds in each piece of work handed out
+        #[arg(long, default_value_t = 1_000_000)]
+        size: u32,
+        /// Give up after this many seconds without any workers connected
+        #[arg(long, default_value_t = 600)]
+        timeout: u64,
+        /// Hand a unit to another worker once its worker has gone this many seconds without a word
+        #[arg(long, default_value_t = 30)]
+        worker_timeout: u64,
+    },
+    /// Search whatever the coordinator at `addr` hands over
+    Work { addr: String },
+}
+
+fn parse_spell(s: &str) -> Result<Spell, String> {
+    s.parse().map_err(|_| format!("unknown spell {}", s))
+}
+
+fn parse_version(s: &str) -> Result<GameVersion, String> {
+    s.parse().map_err(|_| format!("unknown game version {}", s))
+}
+
+/// A helper at the seed and position to start from, with `len` values ready.
+/// Those given on the command line win, else they come from the session along with its party and enemy
+fn start(
+    character: &Character,
+    seed: Option<u32>,
+    position: Option<u32>,
+    session: &Option<Session>,
+    len: usize,
+) -> Result<RNGHelper, Box<dyn Error>> {
+    let mut start = match (seed, session) {
+        (Some(seed), Some(session)) => Session {
+            seed,
+            position: 0,
+            ..session.clone()
+        },
+        (Some(seed), None) => Session {
+            character: *character,
+            seed,
+            ..Session::default()
+        },
+        (None, Some(session)) => session.clone(),
+        (None, None) => return Err("needs a --seed, or a --session to take it from".into()),
+    };
+    if let Some(position) = position {
+        start.position = position;
+    }
+    Ok(start.restore(len))
+}
+
+/// Saves where `helper` is up to, keeping everything else the session already held
+fn save_session(
+    path: &Option<PathBuf>,
+    character: &Character,
+    helper: &RNGHelper,
+    values: &[i32],
+    previous: &Option<Session>,
+) -> Result<(), Box<dyn Error>> {
+    if let Some(path) = path {
+        let observations: Vec<(usize, i32)> = values.iter().map(|value| (0, *value)).collect();
+        let session = match previous {
+            Some(session) => Session {
+                seed: helper.rng.seed,
+                position: helper.position(),
+                observations,
+                ..session.clone()
+            },
+            None => Session::capture(character, helper, &observations, &[]),
+        };
+        session.save(path)?;
+    }
+    Ok(())
+}
+
+fn main() {
+    let cli = Cli::parse();
+    if let Err(err) = run(cli) {
+        eprintln!("error: {}", err);
+        std::process::exit(1);
+    }
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
+    let session = match &cli.session {
+        Some(path) if path.exists() => Some(Session::load(path)?),
+        _ => None,
+    };
+    let (character, enemy) = match &session {
+        Some(session) => (session.character, session.enemy),
+        None => (cli.character.character(), Enemy::default()),
+    };
+    match cli.command {
+        Command::FindSeed {
+            values,
+            min,
+            max,
+            iters,
+            checkpoint,
+            index,
+        } => {
+            let matches: Vec<output::Match> = if let Some(path) = index {
+                SeedIndex::open(&path)?
+                    .lookup(&character, &values)?
+                    .iter()
+                    .map(|candidate| output::Match {
+                        seed: candidate.seed,
+                        position: candidate.offset,
+                    })
+                    .collect()
+            } else {
+                let found = match checkpoint {
+                    Some(path) => {
+                        let fresh = SearchJob::new(&character, &enemy, &values, min, max, iters);
+                        let mut job = if path.exists() {
+                            let saved = SearchJob::load(&path)?;
+                            if !saved.is_same_search(&fresh) {
+                                return Err(format!(
+                                    "checkpoint {} is for a different search: give the same heals, \
+                                     character, --min, --max and --iters, or remove it",
+                                    path.display()
+                                )
+                                .into());
+                            }
+                            saved
+                        } else {
+                            fresh
+                        };
+                        job.run(|job| {
+                            if let Err(err) = job.save(&path) {
+                                eprintln!("Couldn't save checkpoint: {}", err);
+                            }
+                        })
+                    }
+                    None => RNGHelper::find_seed(&character, &enemy, &values, min, max, iters),
+                };
+                if let Some(helper) = &found {
+                    save_session(&cli.session, &character, helper, &values, &session)?;
+                }
+                found
+                    .map(|helper| output::Match {
+                        seed: helper.rng.seed,
+                        position: helper.position(),
+                    })
+                    .into_iter()
+                    .collect()
+            };
+            output::print(&matches, cli.format)
+        }
+        Command::FindNext {
+            values,
+            seed,
+            position,
+            limit,
+        } => {
+            let mut helper = start(&character, seed, position, &session, values.len())?;
+            let mut matches = vec![];
+            if helper.find_casts(&character, &values, Some(limit)) {
+                save_session(&cli.session, &character, &helper, &values, &session)?;
+                matches.push(output::Match {
+                    seed: helper.rng.seed,
+                    position: helper.position(),
+                });
+            }
+            output::print(&matches, cli.format)
+        }
+        Command::Table {
+            seed,
+            position,
+            count,
+        } => {
+            let helper = start(&character, seed, position, &session, count)?;
+            let rows: Vec<output::Value> = helper
+                .values
+                .iter()
+                .map(|lens| output::Value {
+                    position: lens.position - 1,
+                    value: lens.value,
+                    heal: lens.spell,
+                    chest: lens.chest,
+                })
+                .collect();
+            output::print(&rows, cli.format)
+        }
+        Command::Chest {
+            seed,
+            position,
+            chance,
+            count,
+            limit,
+        } => {
+            let helper = start(&character, seed, position, &session, limit as usize)?;
+            let rows: Vec<output::Value> = helper
+                .values
+                .iter()
+                .filter(|lens| lens.chest < chance)
+                .take(count)
+                .map(|lens| output::Value {
+                    position: lens.position - 1,
+                    value: lens.value,
+                    heal: lens.spell,
+                    chest: lens.chest,
+                })
+                .collect();
+            output::print(&rows, cli.format)
+        }
+        Command::Shard {
+            command:
+                ShardCommand::Serve {
+                    values,
+                    bind,
+                    min,
+                    max,
+                    iters,
+                    size,
+                    timeout,
+                    worker_timeout,
+                },
+        } => {
+            let units = shard::shards(character.version, min, max, size);
+            let job = ShardJob {
+                character,
+                enemy,
+                values: values.clone(),
+                iters,
+            };
+            let listener = TcpListener::bind(&bind)?;
+            eprintln!(
+                "Handing out {} units to workers on {}",
+                units.len(),
+                listener.local_addr()?
+            );
+            let found = Coordinator::new(job, units)
+                .timeout(Duration::from_secs(timeout))
+                .worker_timeout(Duration::from_secs(worker_timeout))
+                .serve(listener)?;
+            if let Some(helper) = &found {
+                save_session(&cli.session, &character, helper, &values, &session)?;
+            }
+            let matches: Vec<output::Match> = found
+                .map(|helper| output::Match {
+                    seed: helper.rng.seed,
+                    position: helper.position(),
+                })
+                .into_iter()
+                .collect();
+            output::print(&matches, cli.format)
+        }
+        Command::Shard {
+            command: ShardCommand::Work { addr },
+        } => Ok(shard::work(addr)?),
+        Command::Tui { min, max, iters } => {
+            let latest = tui::run(character, session, min, max, iters)?;
+            if let Some(path) = &cli.session {
+                latest.save(path)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn verify_cli() {
+        Cli::command().debug_assert();
+    }
+}
@@ -0,0 +1,103 @@
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_derive::Serialize;
+use std::error::Error;
+use std::io;
+
+#[derive(ValueEnum, Debug, Copy, Clone, PartialEq)]
+pub enum Format {
+    /// Columns lined up for reading
+    Plain,
+    Json,
+    Csv,
+}
+
+/// Something printed as one line of a table
+pub trait Row: Serialize {
+    fn headers() -> Vec<&'static str>;
+    fn cells(&self) -> Vec<String>;
+}
+
+/// A seed, and how many values had been used before the first matched value
+#[derive(Serialize, Debug)]
+pub struct Match {
+    pub seed: u32,
+    pub position: u32,
+}
+
+impl Row for Match {
+    fn headers() -> Vec<&'static str> {
+        vec!["Seed", "Position"]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![self.seed.to_string(), self.position.to_string()]
+    }
+}
+
+/// One position of the RNG
+#[derive(Serialize, Debug)]
+pub struct Value {
+    pub position: u32,
+    pub value: u32,
+    pub heal: i32,
+    pub chest: u8,
+}
+
+impl Row for Value {
+    fn headers() -> Vec<&'static str> {
+        vec!["Pos", "Value", "Heal", "Chest"]
+    }
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.position.to_string(),
+            self.value.to_string(),
+            self.heal.to_string(),
+            self.chest.to_string(),
+        ]
+    }
+}
+
+pub fn print<R: Row>(rows: &[R], format: Format) -> Result<(), Box<dyn Error>> {
+    match format {
+        Format::Plain => print_plain(rows),
+        Format::Json => println!("{}", serde_json::to_string_pretty(rows)?),
+        Format::Csv => {
+            let mut writer = csv::Writer::from_writer(io::stdout());
+            for row in rows {
+                writer.serialize(row)?;
+            }
+            writer.flush()?;
+        }
+    }
+    Ok(())
+}
+
+fn print_plain<R: Row>(rows: &[R]) {
+    let headers = R::headers();
+    let cells: Vec<Vec<String>> = rows.iter().map(|row| row.cells()).collect();
+    let widths: Vec<usize> = headers
+        .iter()
+        .enumerate()
+        .map(|(idx, header)| {
+            cells
+                .iter()
+                .map(|row| row[idx].len())
+                .chain(Some(header.len()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let line = |row: Vec<String>| {
+        row.iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:>width$}", cell, width = width))
+            .collect::<Vec<String>>()
+            .join("  ")
+    };
+    println!("{}", line(headers.iter().map(|h| h.to_string()).collect()));
+    for row in cells {
+        println!("{}", line(row));
+    }
+}
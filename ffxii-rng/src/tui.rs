@@ -0,0 +1,428 @@
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Cell, Paragraph, Row, Table};
+use ratatui::{DefaultTerminal, Frame};
+use std::io;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::Duration;
+
+use ffxii_tza_rng::character::Character;
+use ffxii_tza_rng::enemy::Enemy;
+use ffxii_tza_rng::rng_helper::RNGHelper;
+use ffxii_tza_rng::session::Session;
+use ffxii_tza_rng::spell::Spell;
+
+/// How many values the results table holds
+const WINDOW: usize = 500;
+
+/// The field arrow keys and typing act on
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Focus {
+    Level,
+    Magic,
+    Spell,
+    Serenity,
+    Faith,
+    Cures,
+    Seed,
+}
+
+impl Focus {
+    const ALL: [Focus; 7] = [
+        Focus::Level,
+        Focus::Magic,
+        Focus::Spell,
+        Focus::Serenity,
+        Focus::Faith,
+        Focus::Cures,
+        Focus::Seed,
+    ];
+
+    fn next(self) -> Focus {
+        let idx = Focus::ALL.iter().position(|focus| *focus == self).unwrap();
+        Focus::ALL[(idx + 1) % Focus::ALL.len()]
+    }
+
+    fn prev(self) -> Focus {
+        let idx = Focus::ALL.iter().position(|focus| *focus == self).unwrap();
+        Focus::ALL[(idx + Focus::ALL.len() - 1) % Focus::ALL.len()]
+    }
+}
+
+struct App {
+    character: Character,
+    rng_helper: RNGHelper,
+    focus: Focus,
+    /// Heals typed in, separated by spaces
+    cures: String,
+    /// Seed typed in, applied with enter
+    seed: String,
+    min: u32,
+    max: u32,
+    iters: usize,
+    /// How far down the results table is scrolled
+    scroll: usize,
+    /// Carried over from the session so they aren't lost on saving
+    targets: Vec<u32>,
+    status: String,
+    search: Option<Receiver<Option<RNGHelper>>>,
+    quit: bool,
+}
+
+impl App {
+    fn new(
+        character: Character,
+        session: Option<Session>,
+        min: u32,
+        max: u32,
+        iters: usize,
+    ) -> App {
+        let (character, rng_helper, cures, targets) = match session {
+            Some(session) => {
+                let cures: Vec<String> = session
+                    .observations
+                    .iter()
+                    .map(|(_, value)| value.to_string())
+                    .collect();
+                (
+                    session.character,
+                    session.restore(WINDOW),
+                    cures.join(" "),
+                    session.targets,
+                )
+            }
+            None => (
+                character,
+                RNGHelper::new(None, &character, WINDOW),
+                String::new(),
+                vec![],
+            ),
+        };
+        App {
+            character,
+            seed: rng_helper.rng.seed.to_string(),
+            rng_helper,
+            focus: Focus::Cures,
+            cures,
+            min,
+            max,
+            iters,
+            scroll: 0,
+            targets,
+            status: "Tab to move between fields, ? for keys".to_string(),
+            search: None,
+            quit: false,
+        }
+    }
+
+    fn cure_values(&self) -> Vec<i32> {
+        self.cures
+            .split_whitespace()
+            .filter_map(|value| value.parse().ok())
+            .collect()
+    }
+
+    fn session(&self) -> Session {
+        let observations: Vec<(usize, i32)> = self
+            .cure_values()
+            .into_iter()
+            .map(|value| (0, value))
+            .collect();
+        Session::capture(
+            &self.character,
+            &self.rng_helper,
+            &observations,
+            &self.targets,
+        )
+    }
+
+    fn apply_character(&mut self) {
+        self.rng_helper.apply_character(&self.character);
+    }
+
+    fn set_seed(&mut self, seed: u32) {
+        self.rng_helper = RNGHelper::new(Some(seed), &self.character, WINDOW);
+        self.seed = seed.to_string();
+        self.scroll = 0;
+    }
+
+    fn start_search(&mut self) {
+        let values = self.cure_values();
+        if values.is_empty() {
+            self.status = "Enter some cure values first".to_string();
+            return;
+        }
+        if self.search.is_some() {
+            return;
+        }
+        let (sender, receiver) = mpsc::channel();
+        let (character, min, max, iters) = (self.character, self.min, self.max, self.iters);
+        thread::spawn(move || {
+            let found =
+                RNGHelper::find_seed(&character, &Enemy::default(), &values, min, max, iters);
+            let _ = sender.send(found);
+        });
+        self.search = Some(receiver);
+        self.status = "Searching...".to_string();
+    }
+
+    /// Picks up the search result, if the search has finished
+    fn poll_search(&mut self) {
+        let result = match &self.search {
+            Some(receiver) => receiver.try_recv(),
+            None => return,
+        };
+        match result {
+            Ok(Some(helper)) => {
+                self.search = None;
+                self.seed = helper.rng.seed.to_string();
+                self.status = format!(
+                    "Found seed {} at position {}",
+                    helper.rng.seed,
+                    helper.position()
+                );
+                self.rng_helper = helper;
+                while self.rng_helper.values.len() < WINDOW {
+                    self.rng_helper.push(&self.character);
+                }
+                self.scroll = 0;
+            }
+            Ok(None) | Err(TryRecvError::Disconnected) => {
+                self.search = None;
+                self.status = "No seed found".to_string();
+            }
+            Err(TryRecvError::Empty) => {}
+        }
+    }
+
+    fn next_match(&mut self) {
+        let values = self.cure_values();
+        if values.is_empty() {
+            self.status = "Enter some cure values first".to_string();
+            return;
+        }
+        if self.rng_helper.find_casts(&self.character, &values, None) {
+            self.status = format!("Matched at position {}", self.rng_helper.position());
+            self.scroll = 0;
+        } else {
+            self.status = "No match within the limit".to_string();
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => self.quit = true,
+            KeyCode::Tab | KeyCode::Down => self.focus = self.focus.next(),
+            KeyCode::BackTab | KeyCode::Up => self.focus = self.focus.prev(),
+            KeyCode::Left => self.adjust(-1),
+            KeyCode::Right => self.adjust(1),
+            KeyCode::PageDown => {
+                self.scroll = (self.scroll + 10).min(self.rng_helper.values.len().saturating_sub(1))
+            }
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::Enter => match self.focus {
+                Focus::Seed => match self.seed.parse::<u32>() {
+                    Ok(seed) => self.set_seed(seed),
+                    Err(_) => self.status = "Seed should be a number".to_string(),
+                },
+                _ => self.next_match(),
+            },
+            KeyCode::Backspace => {
+                if let Some(text) = self.text_mut() {
+                    text.pop();
+                }
+            }
+            KeyCode::Char(c) => match self.text_mut() {
+                Some(text) if c.is_ascii_digit() || c == ' ' || c == '-' => text.push(c),
+                _ => self.hotkey(c),
+            },
+            _ => {}
+        }
+    }
+
+    /// The text being typed into, if the focused field takes text
+    fn text_mut(&mut self) -> Option<&mut String> {
+        match self.focus {
+            Focus::Cures => Some(&mut self.cures),
+            Focus::Seed => Some(&mut self.seed),
+            _ => None,
+        }
+    }
+
+    fn hotkey(&mut self, c: char) {
+        match c {
+            'q' => self.quit = true,
+            'a' => {
+                self.rng_helper.next(&self.character);
+                self.status = format!("At position {}", self.rng_helper.position());
+            }
+            'n' => self.next_match(),
+            's' => self.start_search(),
+            '?' => {
+                self.status =
+                    "a: advance a cast  n: next match  s: find seed  PgUp/PgDn: scroll  q: quit"
+                        .to_string()
+            }
+            _ => {}
+        }
+    }
+
+    /// Steps the focused setting up or down
+    fn adjust(&mut self, by: i32) {
+        let step = |value: u8| (value as i32 + by).clamp(1, 99) as u8;
+        match self.focus {
+            Focus::Level => self.character.level = step(self.character.level),
+            Focus::Magic => self.character.magic = step(self.character.magic),
+            Focus::Spell => {
+                let len = Spell::ALL.len() as i32;
+                let idx = Spell::ALL
+                    .iter()
+                    .position(|spell| *spell == self.character.spell)
+                    .unwrap_or(0) as i32;
+                self.character.spell = Spell::ALL[((idx + by).rem_euclid(len)) as usize];
+            }
+            Focus::Serenity => self.character.serenity = !self.character.serenity,
+            Focus::Faith => self.character.faith = !self.character.faith,
+            Focus::Cures | Focus::Seed => return,
+        }
+        self.apply_character();
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [side, results] =
+            Layout::horizontal([Constraint::Length(34), Constraint::Min(0)]).areas(frame.area());
+        let [character, seed, status] = Layout::vertical([
+            Constraint::Length(7),
+            Constraint::Length(5),
+            Constraint::Min(0),
+        ])
+        .areas(side);
+
+        self.draw_character(frame, character);
+        self.draw_seed(frame, seed);
+        frame.render_widget(
+            Paragraph::new(self.status.as_str())
+                .wrap(ratatui::widgets::Wrap { trim: true })
+                .block(Block::bordered().title("Status")),
+            status,
+        );
+        self.draw_results(frame, results);
+    }
+
+    fn field(&self, focus: Focus, label: &str, value: String) -> Line<'_> {
+        let style = if self.focus == focus {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        };
+        Line::from(vec![
+            Span::raw(format!("{:<10}", label)),
+            Span::styled(value, style),
+        ])
+    }
+
+    fn draw_character(&self, frame: &mut Frame, area: Rect) {
+        let yes_no = |on: bool| if on { "Yes" } else { "No" }.to_string();
+        let lines = vec![
+            self.field(Focus::Level, "Level", self.character.level.to_string()),
+            self.field(Focus::Magic, "Magic", self.character.magic.to_string()),
+            self.field(
+                Focus::Spell,
+                "Spell",
+                self.character.spell.name().to_string(),
+            ),
+            self.field(Focus::Serenity, "Serenity", yes_no(self.character.serenity)),
+            self.field(Focus::Faith, "Faith", yes_no(self.character.faith)),
+        ];
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title("Character")),
+            area,
+        );
+    }
+
+    fn draw_seed(&self, frame: &mut Frame, area: Rect) {
+        let lines = vec![
+            self.field(Focus::Cures, "Cures", format!("{}_", self.cures)),
+            self.field(Focus::Seed, "Seed", self.seed.clone()),
+            Line::from(format!("{:<10}{}", "Position", self.rng_helper.position())),
+        ];
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title("Seed")),
+            area,
+        );
+    }
+
+    fn draw_results(&self, frame: &mut Frame, area: Rect) {
+        let cures = self.cure_values();
+        let rows = self
+            .rng_helper
+            .values
+            .iter()
+            .enumerate()
+            .skip(self.scroll)
+            .map(|(idx, lens)| {
+                let style = if cures.get(idx) == Some(&lens.spell) {
+                    Style::default().fg(Color::Green)
+                } else {
+                    Style::default()
+                };
+                Row::new(vec![
+                    Cell::from((lens.position - 1).to_string()),
+                    Cell::from(lens.value.to_string()),
+                    Cell::from(lens.spell.to_string()),
+                    Cell::from(lens.chest.to_string()),
+                ])
+                .style(style)
+            });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(8),
+                Constraint::Length(12),
+                Constraint::Length(8),
+                Constraint::Length(6),
+            ],
+        )
+        .header(
+            Row::new(vec!["Pos", "Value", "Heal", "Chest"])
+                .style(Style::default().add_modifier(Modifier::BOLD)),
+        )
+        .block(Block::bordered().title("Results"));
+        frame.render_widget(table, area);
+    }
+}
+
+/// Runs the terminal UI until the user quits, picking up from `session` if there is one.
+/// Returns the session as it was left
+pub fn run(
+    character: Character,
+    session: Option<Session>,
+    min: u32,
+    max: u32,
+    iters: usize,
+) -> io::Result<Session> {
+    let mut terminal = ratatui::init();
+    let mut app = App::new(character, session, min, max, iters);
+    let result = run_app(&mut terminal, &mut app);
+    ratatui::restore();
+    result.map(|_| app.session())
+}
+
+fn run_app(terminal: &mut DefaultTerminal, app: &mut App) -> io::Result<()> {
+    while !app.quit {
+        terminal.draw(|frame| app.draw(frame))?;
+        app.poll_search();
+        // Wake up now and then so a finished search shows without a key press
+        if event::poll(Duration::from_millis(200))? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    app.handle_key(key);
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use serde_derive::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::steal;
+
+/// Something done in game, and how it moves the RNG along
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum Action {
+    /// A single target spell, such as Cure
+    Cast,
+    /// A spell hitting several targets, taking one draw each
+    CastMultiTarget(u8),
+    /// A physical attack: the hit roll and the damage variance
+    Attack,
+    /// Opening a chest: whether it holds gil, then what it holds
+    OpenChest,
+    Poach,
+    /// Stealing, which rolls for each tier in turn until one succeeds
+    Steal(steal::StealOutcome),
+    /// Loading a zone, which takes a number of draws particular to that zone
+    ZoneTransition(u32),
+    /// Opening the menu, equipping, moving about; anything which doesn't touch the RNG
+    Menu,
+}
+
+impl Action {
+    /// The common actions with a fixed draw count. Steals and zone changes vary, so aren't here
+    pub const TABLE: [Action; 5] = [
+        Action::Cast,
+        Action::Attack,
+        Action::OpenChest,
+        Action::Poach,
+        Action::Menu,
+    ];
+
+    /// How many RNG values the action consumes
+    pub fn draws(&self) -> u32 {
+        match *self {
+            Action::Cast => 1,
+            Action::CastMultiTarget(targets) => targets as u32,
+            Action::Attack => 2,
+            Action::OpenChest => 2,
+            Action::Poach => 1,
+            Action::Steal(outcome) => outcome.draws() as u32,
+            Action::ZoneTransition(draws) => draws,
+            Action::Menu => 0,
+        }
+    }
+
+    /// The position the RNG is at after taking `actions` in order from `position`
+    pub fn position_after(position: u32, actions: &[Action]) -> u32 {
+        position + actions.iter().map(|action| action.draws()).sum::<u32>()
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Action::Cast => write!(f, "Cast"),
+            Action::CastMultiTarget(targets) => write!(f, "Cast on {} targets", targets),
+            Action::Attack => write!(f, "Attack"),
+            Action::OpenChest => write!(f, "Open chest"),
+            Action::Poach => write!(f, "Poach"),
+            Action::Steal(_) => write!(f, "Steal"),
+            Action::ZoneTransition(_) => write!(f, "Change zone"),
+            Action::Menu => write!(f, "Menu"),
+        }
+    }
+}
+
+impl FromStr for Action {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        Action::TABLE
+            .iter()
+            .find(|action| action.to_string() == s)
+            .copied()
+            .ok_or(())
+    }
+}
@@ -0,0 +1,56 @@
+//! Builds a seed index for the heals of a spell or item.
+//!
+//! Usage: build_index <out> <min> <max> [spell or item] [depth] [offsets]
+use std::env;
+use std::path::Path;
+use std::process;
+
+use ffxii_tza_rng::{character, index, item, spell};
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.len() < 3 {
+        eprintln!("Usage: build_index <out> <min> <max> [spell or item] [depth] [offsets]");
+        process::exit(1);
+    }
+    let arg = |idx: usize, default: u32| -> u32 {
+        args.get(idx)
+            .map(|arg| {
+                arg.parse()
+                    .expect("arguments other than the spell should be numbers")
+            })
+            .unwrap_or(default)
+    };
+
+    // Stats don't change the residues, so any character with the spell or item will do
+    let mut character = character::Character::default();
+    if let Some(name) = args.get(3) {
+        match (name.parse::<spell::Spell>(), name.parse::<item::Item>()) {
+            (Ok(spell), _) => character.spell = spell,
+            (_, Ok(item)) => character.item = Some(item),
+            _ => {
+                eprintln!("Unknown spell or item: {}", name);
+                process::exit(1);
+            }
+        }
+    }
+    let modulus = match character.heal_modulus() {
+        Some(modulus) => modulus,
+        None => {
+            eprintln!("Heals from {} don't depend on the RNG", args[3]);
+            process::exit(1);
+        }
+    };
+    let (min, max) = (arg(1, 0), arg(2, 0));
+    let depth = arg(4, 4) as u16;
+    let offsets = arg(5, 16);
+
+    println!(
+        "Indexing seeds {}..{} modulo {} at depth {} over {} offsets",
+        min, max, modulus, depth, offsets
+    );
+    if let Err(err) = index::build(Path::new(&args[0]), modulus, min, max, depth, offsets) {
+        eprintln!("Couldn't build the index: {}", err);
+        process::exit(1);
+    }
+}
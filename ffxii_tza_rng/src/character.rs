@@ -1,39 +1,256 @@
-use crate::spell;
+use crate::{damage, enemy, evasion, item, spell, status, version};
 
 use serde_derive::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
 pub struct Character {
+    pub version: version::GameVersion,
     pub level: u8,
     pub magic: u8,
+    pub strength: u8,
+    pub vitality: u8,
+    pub speed: u8,
     pub spell: spell::Spell,
+    /// Healing item used instead of `spell`, if any
+    pub item: Option<item::Item>,
+    pub weapon: damage::Weapon,
+    pub evasion: evasion::Evasion,
+    /// Magick or technick used to inflict a status, if any
+    pub status_action: Option<status::StatusAction>,
     pub serenity: bool,
+    pub faith: bool,
+    /// Magick added by Magick Lore augments
+    pub magick_lore: u8,
+    /// How many allies a Cura, Curaga or Curaja hits
+    pub targets: u8,
+    /// Whether cures are being cast on an undead enemy, which they hurt instead.
+    /// Curaja and Renew can only be cast on allies, so they still heal
+    pub undead_target: bool,
+    pub brawler: bool,
+    pub berserk: bool,
+    pub bravery: bool,
+    pub genji_gloves: bool,
+    pub blind: bool,
+    /// How many Potion Lore augments are held
+    pub potion_lore: u8,
 }
 
 impl Character {
     pub fn new(level: u8, magic: u8, spell: spell::Spell, serenity: bool) -> Character {
         Character {
+            version: version::GameVersion::default(),
             level,
             magic,
+            strength: 40,
+            vitality: 40,
+            speed: 40,
             spell,
+            item: None,
+            weapon: damage::Weapon::default(),
+            evasion: evasion::Evasion::default(),
+            status_action: None,
             serenity,
+            faith: false,
+            magick_lore: 0,
+            targets: 1,
+            undead_target: false,
+            brawler: false,
+            berserk: false,
+            bravery: false,
+            genji_gloves: false,
+            blind: false,
+            potion_lore: 0,
         }
     }
 
+    /// Magick used by spells, including Magick Lore augments
+    fn magick(&self) -> f64 {
+        self.magic as f64 + self.magick_lore as f64
+    }
+
     fn base_multiplier(&self) -> f64 {
-        (2.0 + self.magic as f64 * (self.level + self.magic) as f64 / 256.0)
+        (2.0 + self.magick() * (self.level as f64 + self.magick()) / 256.0)
+            * (if self.faith { 1.3 } else { 1.0 })
             * (if self.serenity { 1.5 } else { 1.0 })
     }
 
-    pub fn cast(&self, rng_val: u32) -> i32 {
-        let bonus: f64 =
-            (rng_val % (self.spell.power() as f64 * 12.5).floor() as u32) as f64 / 100.0;
-        self.calculate_heal(bonus)
+    /// How many allies the spell hits. Cure and offensive spells always hit one target
+    pub fn targets(&self) -> usize {
+        match self.spell {
+            spell::Spell::Cura | spell::Spell::Curaga | spell::Spell::Curaja => {
+                self.targets.max(1) as usize
+            }
+            _ => 1,
+        }
+    }
+
+    /// The outcome of casting `spell` with `rng_val` as the bonus draw:
+    /// HP restored for cures, or damage done to `enemy` for offensive spells
+    pub fn cast(&self, rng_val: u32, enemy: &enemy::Enemy) -> i32 {
+        // Renew fully restores HP, so the bonus draw doesn't matter
+        if self.spell == spell::Spell::Renew {
+            return damage::DAMAGE_CAP;
+        }
+        let bonus: f64 = (rng_val % (self.spell.power(self.version) as f64 * 12.5).floor() as u32)
+            as f64
+            / 100.0;
+        if self.spell.is_cure() && !self.hurts_undead() {
+            self.calculate_heal(bonus)
+        } else {
+            self.calculate_magick_damage(bonus, enemy)
+        }
+    }
+
+    /// Whether the spell is a cure cast on an undead enemy
+    fn hurts_undead(&self) -> bool {
+        self.undead_target && self.spell != spell::Spell::Curaja
+    }
+
+    /// HP restored by the healing item, if one is used
+    pub fn use_item(&self, rng_val: u32) -> Option<i32> {
+        self.item.map(|item| item.heal(rng_val, self.potion_lore))
+    }
+
+    /// The observed value for `rng_val`: the item heal if an item is used, otherwise the cast
+    pub fn outcome(&self, rng_val: u32, enemy: &enemy::Enemy) -> i32 {
+        match self.use_item(rng_val) {
+            Some(heal) => heal,
+            None => self.cast(rng_val, enemy),
+        }
+    }
+
+    /// `outcome` only sees `rng_val` modulo this, so values with the same residue heal the same.
+    /// `None` for Renew and Elixirs, which don't use the draw at all
+    pub fn heal_modulus(&self) -> Option<u32> {
+        let power = match self.item {
+            Some(item) => item.power() as f64,
+            None => self.spell.power(self.version) as f64,
+        };
+        match (power * 12.5).floor() as u32 {
+            0 => None,
+            modulus => Some(modulus),
+        }
+    }
+
+    /// Every residue modulo `heal_modulus` which gives `heal` from `outcome`
+    pub fn heal_residues(&self, heal: i32) -> Vec<u32> {
+        let enemy = enemy::Enemy::default();
+        match self.heal_modulus() {
+            Some(modulus) => (0..modulus)
+                .filter(|residue| self.outcome(*residue, &enemy) == heal)
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    /// How many RNG values one cast or item use takes.
+    /// Each target of a multi-target cast takes its own bonus draw, in order
+    pub fn draws(&self) -> usize {
+        match self.item {
+            Some(_) => 1,
+            None => self.targets(),
+        }
     }
 
+    /// Multi-target cures split the heal evenly between their targets
     fn calculate_heal(&self, bonus: f64) -> i32 {
-        let total_power = self.spell.power() as f64 + bonus;
-        (total_power * self.base_multiplier()) as i32
+        let total_power = self.spell.power(self.version) as f64 + bonus;
+        ((total_power * self.base_multiplier() / self.targets() as f64) as i32)
+            .min(damage::DAMAGE_CAP)
+    }
+
+    fn calculate_magick_damage(&self, bonus: f64, enemy: &enemy::Enemy) -> i32 {
+        let total_power =
+            (self.spell.power(self.version) as f64 + bonus - enemy.magick_defense as f64).max(0.0);
+        let affinity = match self.spell.element() {
+            Some(element) => enemy.affinity(element).multiplier(),
+            None => 1.0,
+        };
+        let reverse = if enemy.reverse { -1.0 } else { 1.0 };
+        // Cura and Curaga split the damage between undead the same way they split a heal
+        let damage = (total_power * self.base_multiplier() * affinity * reverse
+            / self.targets() as f64) as i32;
+        damage.clamp(-damage::DAMAGE_CAP, damage::DAMAGE_CAP)
+    }
+
+    /// Attack power of the equipped weapon. Unarmed with Brawler scales with level and strength
+    fn attack_power(&self) -> f64 {
+        match self.weapon.kind {
+            damage::WeaponType::Unarmed if self.brawler => {
+                (self.level as f64 + self.strength as f64) / 2.0
+            }
+            _ => self.weapon.attack as f64,
+        }
+    }
+
+    fn buff_multiplier(&self) -> f64 {
+        (if self.berserk { 1.5 } else { 1.0 }) * (if self.bravery { 1.3 } else { 1.0 })
+    }
+
+    /// Whether an attack with `rng_val` as the critical roll is a critical hit
+    pub fn crits(&self, rng_val: u32) -> bool {
+        match self.weapon.kind.crit_chance() {
+            Some(chance) => rng_val % 100 < chance,
+            None => false,
+        }
+    }
+
+    /// Whether an attack with `rng_val` as the combo roll continues the combo
+    pub fn combos(&self, rng_val: u32) -> bool {
+        match self.weapon.kind.combo_chance(self.genji_gloves) {
+            Some(chance) => rng_val % 100 < chance,
+            None => false,
+        }
+    }
+
+    /// Whether `status_action` inflicts its status on `enemy`, with `rng_val` as the hit roll
+    pub fn inflicts(&self, rng_val: u32, enemy: &enemy::Enemy) -> bool {
+        match self.status_action {
+            Some(action) => rng_val % 100 < enemy.status_chance(action),
+            None => false,
+        }
+    }
+
+    /// Whether a physical attack on `enemy`, with `rng_val` as the hit roll, lands
+    pub fn hits(&self, rng_val: u32, enemy: &enemy::Enemy) -> evasion::HitOutcome {
+        if self.weapon.kind.ignores_evasion() {
+            return evasion::HitOutcome::Hit;
+        }
+        enemy.evasion.check(rng_val, self.blind)
+    }
+
+    /// Whether a physical attack from `enemy`, with `rng_val` as the hit roll, is avoided
+    pub fn defends(&self, rng_val: u32, enemy: &enemy::Enemy) -> evasion::HitOutcome {
+        self.evasion.check(rng_val, enemy.blind)
+    }
+
+    /// Damage done by a physical attack on `enemy`, with `rng_val` as the variance draw
+    pub fn attack(&self, rng_val: u32, enemy: &enemy::Enemy) -> i32 {
+        let kind = self.weapon.kind;
+        let attack = self.attack_power() * kind.variance(rng_val);
+        let damage = if kind.ignores_defense() {
+            attack * self.attack_power()
+        } else {
+            let defense = if kind.uses_magick_defense() {
+                enemy.magick_defense
+            } else {
+                enemy.defense
+            };
+            (attack - defense as f64).max(0.0)
+                * kind.multiplier(
+                    self.level,
+                    self.strength,
+                    self.magic,
+                    self.vitality,
+                    self.speed,
+                )
+        };
+        let damage = ((damage * self.buff_multiplier()) as i32).min(damage::DAMAGE_CAP);
+        if enemy.reverse {
+            -damage
+        } else {
+            damage
+        }
     }
 }
 
@@ -0,0 +1,176 @@
+use serde_derive::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// The most damage a single hit can do
+pub const DAMAGE_CAP: i32 = 9999;
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum WeaponType {
+    Unarmed,
+    Sword,
+    Greatsword,
+    Katana,
+    NinjaSword,
+    Dagger,
+    Spear,
+    Pole,
+    Rod,
+    Staff,
+    Mace,
+    Axe,
+    Hammer,
+    Bow,
+    Crossbow,
+    Gun,
+    HandBomb,
+}
+
+impl WeaponType {
+    pub const ALL: [WeaponType; 17] = [
+        WeaponType::Unarmed,
+        WeaponType::Sword,
+        WeaponType::Greatsword,
+        WeaponType::Katana,
+        WeaponType::NinjaSword,
+        WeaponType::Dagger,
+        WeaponType::Spear,
+        WeaponType::Pole,
+        WeaponType::Rod,
+        WeaponType::Staff,
+        WeaponType::Mace,
+        WeaponType::Axe,
+        WeaponType::Hammer,
+        WeaponType::Bow,
+        WeaponType::Crossbow,
+        WeaponType::Gun,
+        WeaponType::HandBomb,
+    ];
+
+    pub fn name(&self) -> &str {
+        match *self {
+            WeaponType::Unarmed => "Unarmed",
+            WeaponType::Sword => "Sword",
+            WeaponType::Greatsword => "Greatsword",
+            WeaponType::Katana => "Katana",
+            WeaponType::NinjaSword => "Ninja Sword",
+            WeaponType::Dagger => "Dagger",
+            WeaponType::Spear => "Spear",
+            WeaponType::Pole => "Pole",
+            WeaponType::Rod => "Rod",
+            WeaponType::Staff => "Staff",
+            WeaponType::Mace => "Mace",
+            WeaponType::Axe => "Axe",
+            WeaponType::Hammer => "Hammer",
+            WeaponType::Bow => "Bow",
+            WeaponType::Crossbow => "Crossbow",
+            WeaponType::Gun => "Gun",
+            WeaponType::HandBomb => "Hand-bomb",
+        }
+    }
+
+    /// The random multiplier applied to attack power.
+    /// Axes, hammers and hand-bombs roll 0~1.111, everything else rolls 1~1.125
+    pub fn variance(&self, rng_val: u32) -> f64 {
+        let fraction = rng_val as f64 / (u32::MAX as f64 + 1.0);
+        match *self {
+            WeaponType::Axe | WeaponType::Hammer | WeaponType::HandBomb => 1.111 * fraction,
+            _ => 1.0 + 0.125 * fraction,
+        }
+    }
+
+    /// The stat multiplier applied after defense is taken off
+    pub fn multiplier(&self, level: u8, strength: u8, magic: u8, vitality: u8, speed: u8) -> f64 {
+        let (level, strength, magic, vitality, speed) = (
+            level as f64,
+            strength as f64,
+            magic as f64,
+            vitality as f64,
+            speed as f64,
+        );
+        match *self {
+            WeaponType::Unarmed
+            | WeaponType::Sword
+            | WeaponType::Greatsword
+            | WeaponType::Spear
+            | WeaponType::Pole => 1.0 + strength * (level + strength) / 256.0,
+            WeaponType::Katana | WeaponType::Staff => 1.0 + strength * (level + magic) / 256.0,
+            WeaponType::NinjaSword
+            | WeaponType::Dagger
+            | WeaponType::Bow
+            | WeaponType::Crossbow => 1.0 + strength * (level + speed) / 218.0,
+            WeaponType::Rod | WeaponType::Mace => 1.0 + magic * (level + magic) / 256.0,
+            WeaponType::Axe | WeaponType::Hammer | WeaponType::HandBomb => {
+                1.0 + strength * (level + vitality) / 128.0
+            }
+            // Guns don't scale with stats at all
+            WeaponType::Gun => 1.0,
+        }
+    }
+
+    /// Chance out of 100 for a hit to be critical, for weapons which can crit
+    pub fn crit_chance(&self) -> Option<u32> {
+        match *self {
+            WeaponType::Spear | WeaponType::Pole | WeaponType::Bow => Some(5),
+            WeaponType::Rod | WeaponType::Staff | WeaponType::Mace => Some(3),
+            _ => None,
+        }
+    }
+
+    /// Chance out of 100 for a hit to combo into another, for weapons which can combo.
+    /// Genji Gloves raise the chance for every one of them
+    pub fn combo_chance(&self, genji_gloves: bool) -> Option<u32> {
+        let chance = match *self {
+            WeaponType::Unarmed | WeaponType::Sword | WeaponType::Dagger => 5,
+            WeaponType::Greatsword => 3,
+            WeaponType::Katana => 10,
+            WeaponType::NinjaSword => 15,
+            _ => return None,
+        };
+        Some(if genji_gloves { chance + 5 } else { chance })
+    }
+
+    /// Poles hit against magick defense rather than defense
+    pub fn uses_magick_defense(&self) -> bool {
+        *self == WeaponType::Pole
+    }
+
+    /// Guns can't be blocked, parried or evaded
+    pub fn ignores_evasion(&self) -> bool {
+        *self == WeaponType::Gun
+    }
+
+    /// Guns ignore defense, and square their attack power instead
+    pub fn ignores_defense(&self) -> bool {
+        *self == WeaponType::Gun
+    }
+}
+
+impl FromStr for WeaponType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        WeaponType::ALL
+            .iter()
+            .find(|kind| kind.name() == s)
+            .copied()
+            .ok_or(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct Weapon {
+    pub kind: WeaponType,
+    pub attack: u8,
+}
+
+impl Weapon {
+    pub fn new(kind: WeaponType, attack: u8) -> Weapon {
+        Weapon { kind, attack }
+    }
+}
+
+impl Default for Weapon {
+    fn default() -> Self {
+        Weapon::new(WeaponType::Unarmed, 11)
+    }
+}
@@ -0,0 +1,84 @@
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{evasion, spell, status};
+
+/// How an enemy takes damage from an element
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone, PartialEq)]
+pub enum Affinity {
+    #[default]
+    Normal,
+    Weak,
+    Resist,
+    Immune,
+    Absorb,
+}
+
+impl Affinity {
+    pub fn multiplier(&self) -> f64 {
+        match *self {
+            Affinity::Normal => 1.0,
+            Affinity::Weak => 2.0,
+            Affinity::Resist => 0.5,
+            Affinity::Immune => 0.0,
+            Affinity::Absorb => -1.0,
+        }
+    }
+}
+
+/// The stats of the enemy being targeted
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone, PartialEq)]
+pub struct Enemy {
+    pub defense: u8,
+    pub magick_defense: u8,
+    /// Affinity to each element, indexed by `spell::Element`
+    pub affinities: [Affinity; 7],
+    /// Immunity to each status, indexed by `status::Status`
+    pub status_immunities: [bool; 14],
+    /// How much is taken off the chance to inflict any status, out of 100
+    pub status_resistance: u8,
+    pub evasion: evasion::Evasion,
+    pub blind: bool,
+    /// Under Reverse, damage heals the enemy instead
+    pub reverse: bool,
+}
+
+impl Enemy {
+    pub fn new(defense: u8, magick_defense: u8) -> Enemy {
+        Enemy {
+            defense,
+            magick_defense,
+            affinities: [Affinity::Normal; 7],
+            status_immunities: [false; 14],
+            status_resistance: 0,
+            evasion: evasion::Evasion::default(),
+            blind: false,
+            reverse: false,
+        }
+    }
+
+    pub fn affinity(&self, element: spell::Element) -> Affinity {
+        self.affinities[element as usize]
+    }
+
+    pub fn set_affinity(&mut self, element: spell::Element, affinity: Affinity) {
+        self.affinities[element as usize] = affinity;
+    }
+
+    pub fn immune_to(&self, status: status::Status) -> bool {
+        self.status_immunities[status as usize]
+    }
+
+    pub fn set_immunity(&mut self, status: status::Status, immune: bool) {
+        self.status_immunities[status as usize] = immune;
+    }
+
+    /// Chance out of 100 for `action` to inflict its status on this enemy
+    pub fn status_chance(&self, action: status::StatusAction) -> u32 {
+        if self.immune_to(action.status()) {
+            return 0;
+        }
+        action
+            .chance()
+            .saturating_sub(self.status_resistance as u32)
+    }
+}
@@ -0,0 +1,63 @@
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum HitOutcome {
+    Hit,
+    Blocked,
+    Parried,
+    Evaded,
+}
+
+impl HitOutcome {
+    pub fn name(&self) -> &str {
+        match *self {
+            HitOutcome::Hit => "Hit",
+            HitOutcome::Blocked => "Blocked",
+            HitOutcome::Parried => "Parried",
+            HitOutcome::Evaded => "Evaded",
+        }
+    }
+}
+
+/// The defender's chances, out of 100, to avoid a physical attack
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone, PartialEq)]
+pub struct Evasion {
+    /// Shield block chance
+    pub shield: u8,
+    /// Weapon parry chance
+    pub parry: u8,
+    /// Innate evasion
+    pub evade: u8,
+}
+
+impl Evasion {
+    pub fn new(shield: u8, parry: u8, evade: u8) -> Evasion {
+        Evasion {
+            shield,
+            parry,
+            evade,
+        }
+    }
+
+    /// Whether a physical attack with `rng_val` as the hit roll lands.
+    /// The shield is checked first, then parry, then evasion, all off the one roll.
+    /// Blind attackers have every chance doubled
+    pub fn check(&self, rng_val: u32, attacker_blind: bool) -> HitOutcome {
+        let scale = if attacker_blind { 2 } else { 1 };
+        let roll = rng_val % 100;
+        let mut threshold = 0;
+        for (chance, outcome) in [
+            (self.shield, HitOutcome::Blocked),
+            (self.parry, HitOutcome::Parried),
+            (self.evade, HitOutcome::Evaded),
+        ]
+        .iter()
+        {
+            threshold += *chance as u32 * scale;
+            if roll < threshold {
+                return *outcome;
+            }
+        }
+        HitOutcome::Hit
+    }
+}
@@ -0,0 +1,158 @@
+use serde_derive::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::rng_helper::ValueLens;
+
+/// Ways of writing out a window of the RNG table
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum Format {
+    Csv,
+    JsonLines,
+    /// A table lined up for pasting into docs and issue trackers
+    Markdown,
+}
+
+impl Format {
+    pub const ALL: [Format; 3] = [Format::Csv, Format::JsonLines, Format::Markdown];
+
+    pub fn name(&self) -> &str {
+        match *self {
+            Format::Csv => "CSV",
+            Format::JsonLines => "JSON Lines",
+            Format::Markdown => "Markdown",
+        }
+    }
+
+    pub fn extension(&self) -> &str {
+        match *self {
+            Format::Csv => "csv",
+            Format::JsonLines => "jsonl",
+            Format::Markdown => "md",
+        }
+    }
+
+    pub fn mime_type(&self) -> &str {
+        match *self {
+            Format::Csv => "text/csv",
+            Format::JsonLines => "application/jsonl",
+            Format::Markdown => "text/markdown",
+        }
+    }
+
+    pub fn export(&self, rows: &[ValueLens]) -> String {
+        match *self {
+            Format::Csv => csv(rows),
+            Format::JsonLines => json_lines(rows),
+            Format::Markdown => markdown(rows),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        Format::ALL
+            .iter()
+            .find(|format| format.name() == s)
+            .copied()
+            .ok_or(())
+    }
+}
+
+/// One exported row: the parts of a `ValueLens` route planning cares about
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ExportRow {
+    pub position: u32,
+    pub value: u32,
+    /// Each party member's heal, in party order
+    pub heals: Vec<i32>,
+    /// The chest roll, out of 100: a chest appears if this is under its chance
+    pub chest: u8,
+}
+
+impl From<&ValueLens> for ExportRow {
+    fn from(lens: &ValueLens) -> Self {
+        // The first member is the character, whose heal is always up to date in `spell`
+        let mut heals = vec![lens.spell];
+        heals.extend(lens.party.iter().skip(1));
+        ExportRow {
+            position: lens.position,
+            value: lens.value,
+            heals,
+            chest: lens.chest,
+        }
+    }
+}
+
+/// Headers and cells shared by the tabular formats, with a heal column per party member
+fn table(rows: &[ValueLens]) -> Vec<Vec<String>> {
+    let rows: Vec<ExportRow> = rows.iter().map(ExportRow::from).collect();
+    let members = rows.iter().map(|row| row.heals.len()).max().unwrap_or(1);
+
+    let mut headers = vec!["Position".to_string(), "Value".to_string()];
+    headers.extend((1..=members).map(|member| format!("Heal {}", member)));
+    headers.push("Chest".to_string());
+
+    let mut table = vec![headers];
+    for row in rows {
+        let mut cells = vec![row.position.to_string(), row.value.to_string()];
+        cells.extend((0..members).map(|idx| match row.heals.get(idx) {
+            Some(heal) => heal.to_string(),
+            None => String::new(),
+        }));
+        cells.push(row.chest.to_string());
+        table.push(cells);
+    }
+    table
+}
+
+/// Every cell is a number, so nothing needs quoting
+pub fn csv(rows: &[ValueLens]) -> String {
+    table(rows)
+        .iter()
+        .map(|cells| cells.join(",") + "\n")
+        .collect()
+}
+
+pub fn json_lines(rows: &[ValueLens]) -> String {
+    rows.iter()
+        .map(|lens| {
+            serde_json::to_string(&ExportRow::from(lens)).expect("rows always serialize") + "\n"
+        })
+        .collect()
+}
+
+pub fn markdown(rows: &[ValueLens]) -> String {
+    let table = table(rows);
+    let widths: Vec<usize> = (0..table[0].len())
+        .map(|col| {
+            table
+                .iter()
+                .map(|cells| cells[col].len())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let line = |cells: Vec<String>| {
+        let cells: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!(" {:>width$} ", cell, width = width))
+            .collect();
+        format!("|{}|\n", cells.join("|"))
+    };
+
+    let mut out = line(table[0].clone());
+    // Numbers read best right aligned
+    out += &line(
+        widths
+            .iter()
+            .map(|width| format!("{}:", "-".repeat(width.saturating_sub(1))))
+            .collect(),
+    );
+    for cells in table.into_iter().skip(1) {
+        out += &line(cells);
+    }
+    out
+}
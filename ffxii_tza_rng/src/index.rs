@@ -0,0 +1,353 @@
+//! A precomputed index from the first few heals after boot to the seeds that produce them.
+//!
+//! Heals only depend on the RNG through `rng_val % modulus`, where the modulus comes from the
+//! spell or item's power, so the index stores those residues rather than heal values. One index
+//! then serves every character healing with the same modulus, whatever their level or magic.
+//! Observed heals are turned back into the residues which could have produced them to look up.
+//! Heals from weaker characters match more residues each, so lookups narrow the entries down one
+//! heal at a time rather than trying every combination.
+//!
+//! # File format, version 3
+//!
+//! All integers are little endian.
+//!
+//! | Field       | Type      | Notes                                                    |
+//! |-------------|-----------|----------------------------------------------------------|
+//! | magic       | `[u8; 4]` | `b"FXSI"`                                                |
+//! | version     | `u16`     | `FORMAT_VERSION`, bumped on any change to the layout     |
+//! | depth       | `u16`     | How many consecutive heals make up a fingerprint         |
+//! | offsets     | `u32`     | How many starting positions were indexed for each seed   |
+//! | modulus     | `u32`     | The residues are of RNG values modulo this               |
+//! | count       | `u64`     | Number of entries                                        |
+//! | entries     | `count` × | Sorted by key, 16 bytes each, laid out as below          |
+//!
+//! Each entry is a `u64` key, the `u32` seed and the `u32` offset: how many values had been
+//! used before the first heal. The key packs the residues 16 bits apiece, the first in the top
+//! bits, so entries sharing their first few residues sit together. Depth is at most
+//! `MAX_DEPTH` and the modulus below 2^16 so they fit.
+//!
+//! Version 2 keyed entries by an FNV-1a hash of the residues, which could only be looked up
+//! whole. Version 1 stored a `Character` as JSON after the count and hashed its heal values.
+use rayon::prelude::*;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::ops::Range;
+use std::path::Path;
+
+use crate::rng_helper::RNGHelper;
+use crate::{character, enemy, rng};
+
+pub const MAGIC: [u8; 4] = *b"FXSI";
+pub const FORMAT_VERSION: u16 = 3;
+
+/// The most residues a key holds
+pub const MAX_DEPTH: u16 = 4;
+
+const ENTRY_LEN: u64 = 16;
+const HEADER_LEN: u64 = 4 + 2 + 2 + 4 + 4 + 8;
+
+/// Past narrowing this many entries down, it's quicker to read through them all
+const SCAN: u64 = 4_096;
+
+/// Packs consecutive residues into a key, the first in the top 16 bits
+pub fn key(residues: &[u32]) -> u64 {
+    residues
+        .iter()
+        .enumerate()
+        .fold(0, |key, (level, residue)| {
+            key | residue_bits(level, *residue)
+        })
+}
+
+fn residue_bits(level: usize, residue: u32) -> u64 {
+    (residue as u64) << (48 - 16 * level)
+}
+
+fn residue_at(key: u64, level: usize) -> u32 {
+    ((key >> (48 - 16 * level)) & 0xffff) as u32
+}
+
+/// Sorted residues as runs of consecutive ones. Heals rise with the residue, so there's usually one
+fn runs(residues: &[u32]) -> Vec<Range<u32>> {
+    let mut runs: Vec<Range<u32>> = vec![];
+    for residue in residues {
+        match runs.last_mut() {
+            Some(run) if run.end == *residue => run.end += 1,
+            _ => runs.push(*residue..*residue + 1),
+        }
+    }
+    runs
+}
+
+/// Whether every residue in `key` from `level` on falls in its set
+fn matches(key: u64, sets: &[Vec<Range<u32>>], level: usize) -> bool {
+    sets.iter()
+        .enumerate()
+        .skip(level)
+        .all(|(level, runs)| runs.iter().any(|run| run.contains(&residue_at(key, level))))
+}
+
+/// A seed, and where in it a run of heals starts
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Candidate {
+    pub seed: u32,
+    pub offset: u32,
+}
+
+impl Candidate {
+    /// A helper for the candidate, with the first matched heal at its head
+    pub fn helper(&self, character: &character::Character, len: usize) -> RNGHelper {
+        let mut helper = RNGHelper::new(Some(self.seed), character, len);
+        for _ in 0..self.offset {
+            helper.next(character);
+        }
+        helper
+    }
+
+    /// Whether `heals` really are what the character gets from this seed and offset
+    fn verify(&self, character: &character::Character, heals: &[i32]) -> bool {
+        let enemy = enemy::Enemy::default();
+        let mut rng = rng::RNG::from(self.seed);
+        (0..self.offset + heals.len() as u32)
+            .map(|_| character.outcome(rng.gen_rand(), &enemy))
+            .skip(self.offset as usize)
+            .eq(heals.iter().copied())
+    }
+}
+
+/// Builds the index of residues modulo `modulus` over the seeds `min..max` and writes it to `path`.
+/// `Character::heal_modulus` gives the modulus for a character.
+///
+/// Every seed contributes `offsets` entries, so this holds `16 * offsets` bytes per seed in memory
+pub fn build(
+    path: &Path,
+    modulus: u32,
+    min: u32,
+    max: u32,
+    depth: u16,
+    offsets: u32,
+) -> io::Result<()> {
+    if depth == 0 || depth > MAX_DEPTH || modulus == 0 || modulus > u16::MAX as u32 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "depth should be 1 to {} and the modulus under 2^16",
+                MAX_DEPTH
+            ),
+        ));
+    }
+    let mut entries: Vec<(u64, u32, u32)> = (min..max)
+        .into_par_iter()
+        .flat_map_iter(|seed| {
+            let mut rng = rng::RNG::from(seed);
+            let residues: Vec<u32> = (0..offsets + depth as u32)
+                .map(|_| rng.gen_rand() % modulus)
+                .collect();
+            (0..offsets).map(move |offset| {
+                let start = offset as usize;
+                (key(&residues[start..start + depth as usize]), seed, offset)
+            })
+        })
+        .collect();
+    entries.par_sort_unstable();
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    writer.write_all(&MAGIC)?;
+    writer.write_all(&FORMAT_VERSION.to_le_bytes())?;
+    writer.write_all(&depth.to_le_bytes())?;
+    writer.write_all(&offsets.to_le_bytes())?;
+    writer.write_all(&modulus.to_le_bytes())?;
+    writer.write_all(&(entries.len() as u64).to_le_bytes())?;
+    for (key, seed, offset) in entries {
+        writer.write_all(&key.to_le_bytes())?;
+        writer.write_all(&seed.to_le_bytes())?;
+        writer.write_all(&offset.to_le_bytes())?;
+    }
+    writer.flush()
+}
+
+/// An index file opened for lookups. Entries are read from disk as needed
+pub struct SeedIndex {
+    pub depth: u16,
+    pub offsets: u32,
+    pub modulus: u32,
+    pub count: u64,
+    file: BufReader<File>,
+}
+
+impl SeedIndex {
+    pub fn open(path: &Path) -> io::Result<SeedIndex> {
+        let mut file = BufReader::new(File::open(path)?);
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+
+        let mut magic = [0; 4];
+        file.read_exact(&mut magic)?;
+        if magic != MAGIC {
+            return Err(invalid("not a seed index"));
+        }
+        let version = read_u16(&mut file)?;
+        if version != FORMAT_VERSION {
+            return Err(invalid(&format!(
+                "index is format version {}, expected {}",
+                version, FORMAT_VERSION
+            )));
+        }
+        let depth = read_u16(&mut file)?;
+        let offsets = read_u32(&mut file)?;
+        let modulus = read_u32(&mut file)?;
+        let count = read_u64(&mut file)?;
+
+        Ok(SeedIndex {
+            depth,
+            offsets,
+            modulus,
+            count,
+            file,
+        })
+    }
+
+    /// Finds every seed and offset where `character` would heal for `heals`, whatever their stats.
+    /// Needs at least `depth` heals; any beyond that are only used to narrow down the results.
+    /// Errors if the character's heals don't use the index's modulus
+    pub fn lookup(
+        &mut self,
+        character: &character::Character,
+        heals: &[i32],
+    ) -> io::Result<Vec<Candidate>> {
+        let depth = self.depth as usize;
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidInput, msg);
+        if heals.len() < depth {
+            return Err(invalid(format!("the index needs at least {} heals", depth)));
+        }
+        if character.heal_modulus() != Some(self.modulus) {
+            return Err(invalid(format!(
+                "the index is of residues modulo {}, which these heals don't use",
+                self.modulus
+            )));
+        }
+        if character.draws() != 1 {
+            return Err(invalid(
+                "multi-target casts skip values, so can't be looked up".to_string(),
+            ));
+        }
+        let sets: Vec<Vec<Range<u32>>> = heals[..depth]
+            .iter()
+            .map(|heal| runs(&character.heal_residues(*heal)))
+            .collect();
+
+        let mut found = vec![];
+        self.narrow(&sets, 0, 0, 0, self.count, &mut found)?;
+        let mut candidates: Vec<Candidate> = found
+            .into_iter()
+            .filter(|candidate| candidate.verify(character, heals))
+            .collect();
+        candidates.sort_by_key(|candidate| (candidate.seed, candidate.offset));
+        Ok(candidates)
+    }
+
+    /// Collects the entries in `low..high`, which all share `prefix` as their residues before
+    /// `level`, whose residues from `level` on fall in `sets`
+    fn narrow(
+        &mut self,
+        sets: &[Vec<Range<u32>>],
+        level: usize,
+        prefix: u64,
+        low: u64,
+        high: u64,
+        found: &mut Vec<Candidate>,
+    ) -> io::Result<()> {
+        for run in &sets[level] {
+            let start = self.lower_bound(prefix | residue_bits(level, run.start), low, high)?;
+            let end = self.lower_bound(prefix | residue_bits(level, run.end), start, high)?;
+            if level + 1 == sets.len() || end - start <= SCAN {
+                for (key, seed, offset) in self.entries(start, end)? {
+                    if matches(key, sets, level) {
+                        found.push(Candidate { seed, offset });
+                    }
+                }
+                continue;
+            }
+            let mut from = start;
+            for residue in run.clone() {
+                let to = self.lower_bound(prefix | residue_bits(level, residue + 1), from, end)?;
+                if to > from {
+                    let prefix = prefix | residue_bits(level, residue);
+                    self.narrow(sets, level + 1, prefix, from, to, found)?;
+                }
+                from = to;
+            }
+        }
+        Ok(())
+    }
+
+    /// Every seed and offset whose next `depth` RNG values have exactly these residues
+    pub fn lookup_residues(&mut self, residues: &[u32]) -> io::Result<Vec<Candidate>> {
+        if residues.len() != self.depth as usize {
+            return Ok(vec![]);
+        }
+        let target = key(residues);
+        let start = self.lower_bound(target, 0, self.count)?;
+        let end = self.lower_bound(target + 1, start, self.count)?;
+        Ok(self
+            .entries(start, end)?
+            .into_iter()
+            .map(|(_, seed, offset)| Candidate { seed, offset })
+            .collect())
+    }
+
+    /// The first entry in `low..high` with a key of at least `target`
+    fn lower_bound(&mut self, target: u64, mut low: u64, mut high: u64) -> io::Result<u64> {
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.entry(mid)?.0 < target {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        Ok(low)
+    }
+
+    /// Reads the entries `start..end` in one go
+    fn entries(&mut self, start: u64, end: u64) -> io::Result<Vec<(u64, u32, u32)>> {
+        self.file
+            .seek(SeekFrom::Start(HEADER_LEN + start * ENTRY_LEN))?;
+        (start..end)
+            .map(|_| {
+                Ok((
+                    read_u64(&mut self.file)?,
+                    read_u32(&mut self.file)?,
+                    read_u32(&mut self.file)?,
+                ))
+            })
+            .collect()
+    }
+
+    fn entry(&mut self, idx: u64) -> io::Result<(u64, u32, u32)> {
+        self.file
+            .seek(SeekFrom::Start(HEADER_LEN + idx * ENTRY_LEN))?;
+        Ok((
+            read_u64(&mut self.file)?,
+            read_u32(&mut self.file)?,
+            read_u32(&mut self.file)?,
+        ))
+    }
+}
+
+fn read_u16<R: Read>(reader: &mut R) -> io::Result<u16> {
+    let mut bytes = [0; 2];
+    reader.read_exact(&mut bytes)?;
+    Ok(u16::from_le_bytes(bytes))
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
@@ -0,0 +1,68 @@
+use serde_derive::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::damage;
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum Item {
+    Potion,
+    HiPotion,
+    XPotion,
+    Elixir,
+}
+
+impl Item {
+    pub const ALL: [Item; 4] = [Item::Potion, Item::HiPotion, Item::XPotion, Item::Elixir];
+
+    pub fn name(&self) -> &str {
+        match *self {
+            Item::Potion => "Potion",
+            Item::HiPotion => "Hi-Potion",
+            Item::XPotion => "X-Potion",
+            Item::Elixir => "Elixir",
+        }
+    }
+
+    pub fn power(&self) -> u16 {
+        match *self {
+            Item::Potion => 400,
+            Item::HiPotion => 1200,
+            Item::XPotion => 3000,
+            // Elixirs heal a fixed amount, so have no power to scale
+            Item::Elixir => 0,
+        }
+    }
+
+    /// Potency multiplier for the number of Potion Lore augments held
+    fn lore_multiplier(potion_lore: u8) -> f64 {
+        match potion_lore {
+            0 => 1.0,
+            1 => 1.5,
+            2 => 2.0,
+            _ => 3.0,
+        }
+    }
+
+    /// HP restored by the item, with `rng_val` as the bonus draw
+    pub fn heal(&self, rng_val: u32, potion_lore: u8) -> i32 {
+        // Elixirs fully restore HP, so the bonus draw doesn't matter
+        if *self == Item::Elixir {
+            return damage::DAMAGE_CAP;
+        }
+        let bonus: f64 = (rng_val % (self.power() as f64 * 12.5).floor() as u32) as f64 / 100.0;
+        let total_power = self.power() as f64 + bonus;
+        ((total_power * Item::lore_multiplier(potion_lore)) as i32).min(damage::DAMAGE_CAP)
+    }
+}
+
+impl FromStr for Item {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        Item::ALL
+            .iter()
+            .find(|item| item.name() == s)
+            .copied()
+            .ok_or(())
+    }
+}
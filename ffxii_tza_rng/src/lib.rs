@@ -1,8 +1,28 @@
 
+pub mod action;
 pub mod character;
+pub mod damage;
+pub mod enemy;
+pub mod evasion;
+pub mod export;
+pub mod index;
+pub mod item;
+pub mod loot;
+pub mod observation;
+pub mod party;
+pub mod planner;
+pub mod poach;
+pub mod rare_game;
 pub mod rng_helper;
 pub mod rng;
+pub mod search;
+pub mod shard;
+pub mod seeding;
+pub mod session;
 pub mod spell;
+pub mod status;
+pub mod steal;
+pub mod version;
 
 #[cfg(test)]
 mod tests {
@@ -11,7 +31,532 @@ mod tests {
     fn find_seed() {
         let character = character::Character::default();
         let values: Vec<i32> = vec![2255, 2063, 2029, 2211, 2195];
-        let rng_helper = rng_helper::RNGHelper::find_seed(&character, &values, 6_000_000, 6_500_000, 1_000).unwrap();
+        let rng_helper = rng_helper::RNGHelper::find_seed(&character, &enemy::Enemy::default(), &values, 6_000_000, 6_500_000, 1_000).unwrap();
         assert_eq!(rng_helper.rng.seed, 6_357_987);
     }
+
+    #[test]
+    fn seeding_ranges() {
+        let model = seeding::SeedingModel::for_version(version::GameVersion::TZAConsole);
+        let mut ranges = model.ranges(6_000_000, 6_500_000);
+        assert!(ranges[0].likelihood >= ranges[1].likelihood);
+        ranges.sort_by_key(|range| range.min);
+        assert_eq!(ranges[0].min, 6_000_000);
+        assert_eq!(ranges.last().unwrap().max, 6_500_000);
+        assert!(ranges.windows(2).all(|pair| pair[0].max == pair[1].min));
+        assert!(model.ranges(10, 5).is_empty());
+        assert!(seeding::SeedingModel::Uniform.ranges(5, 5).is_empty());
+        assert!(rng_helper::RNGHelper::find_seed(&character::Character::default(), &enemy::Enemy::default(), &vec![2255], 10, 5, 10).is_none());
+    }
+
+    #[test]
+    fn rare_game_spawns() {
+        let rng_helper = rng_helper::RNGHelper::default();
+        let croakadile: rare_game::RareGame = "Croakadile".parse().unwrap();
+        let position = rng_helper.find_rare_game(&croakadile, 3).unwrap();
+        let roll = rng_helper.get(position + 4).unwrap();
+        assert!(roll.value % 100 < 10);
+        assert_eq!(rng_helper.rare_game_spawns(&croakadile, position, 3), Some(true));
+    }
+
+    #[test]
+    fn find_rare_steal() {
+        let rng_helper = rng_helper::RNGHelper::default();
+        let position = rng_helper.find_rare_steal(false).unwrap();
+        let rare = steal::StealOutcome::Stolen(steal::StealTier::Rare);
+        assert_eq!(rng_helper.steal(position, false), Some(rare));
+        for earlier in 0..position {
+            assert_ne!(rng_helper.steal(earlier, false), Some(rare));
+        }
+    }
+
+    #[test]
+    fn loot_drops() {
+        assert_eq!(loot::ChainLevel::from_chain(9), loot::ChainLevel::One);
+        assert_eq!(loot::ChainLevel::from_chain(35), loot::ChainLevel::Four);
+        let mut modifiers = loot::LootModifiers { chain: 0, monograph: true, canopic_jar: false };
+        // Drop roll, slot roll, then the Monograph roll
+        assert_eq!(loot::loot(&[0, 0, 9], &modifiers), Some(loot::Loot { slot: Some(loot::DropSlot::VeryRare), monograph: false }));
+        modifiers.canopic_jar = true;
+        assert_eq!(loot::loot(&[0, 0, 9], &modifiers), Some(loot::Loot { slot: Some(loot::DropSlot::VeryRare), monograph: true }));
+        modifiers.chain = 35;
+        assert_eq!(loot::loot(&[0, 60, 59], &modifiers), Some(loot::Loot { slot: Some(loot::DropSlot::Common), monograph: true }));
+        // Nothing dropping skips the slot roll
+        assert_eq!(loot::loot(&[99, 0], &modifiers), Some(loot::Loot { slot: None, monograph: true }));
+        assert_eq!(loot::loot(&[0, 0], &modifiers), None);
+
+        // The default seed's first rolls, out of 100, are 36, 91, 24, 97, 25
+        let rng_helper = rng_helper::RNGHelper::default();
+        let wolf: loot::DropTable = "Wolf".parse().unwrap();
+        let view = rng_helper.loot_view(&wolf, &modifiers);
+        assert_eq!(view[0], (0, vec!["Wolf Pelt", "Ring Wyrm Scale"]));
+        assert_eq!(view[1], (1, vec!["Ring Wyrm Scale"]));
+        assert_eq!(view[2], (2, vec!["Wolf Pelt", "Ring Wyrm Scale"]));
+    }
+
+    #[test]
+    fn poach_outcomes() {
+        let wolf: poach::PoachTable = "Wolf".parse().unwrap();
+        assert_eq!(wolf.poach(104), poach::PoachOutcome::Rare);
+        assert_eq!(wolf.poach(5), poach::PoachOutcome::Common);
+        assert_eq!(wolf.poach(95), poach::PoachOutcome::Failed);
+        assert_eq!(wolf.item(poach::PoachOutcome::Rare), Some("Prime Pelt"));
+        assert_eq!(wolf.item(poach::PoachOutcome::Failed), None);
+
+        // The default seed's first rolls, out of 100, are 36, 91, 24, 97, 25, 61, 96, 1
+        let rng_helper = rng_helper::RNGHelper::default();
+        let outcomes: Vec<poach::PoachOutcome> = rng_helper.poach_view(&wolf).into_iter().take(8).map(|(_, outcome)| outcome).collect();
+        let (common, failed, rare) = (poach::PoachOutcome::Common, poach::PoachOutcome::Failed, poach::PoachOutcome::Rare);
+        assert_eq!(outcomes, vec![common, common, common, failed, common, common, failed, rare]);
+        assert_eq!(rng_helper.poach(7, &wolf), Some(rare));
+    }
+
+    #[test]
+    fn weapon_damage() {
+        let mut character = character::Character::default();
+        let enemy = enemy::Enemy::new(200, 0);
+        // Guns square their attack power and ignore defense
+        character.weapon = damage::Weapon::new(damage::WeaponType::Gun, 50);
+        assert_eq!(character.attack(0, &enemy), 2500);
+        character.berserk = true;
+        assert_eq!(character.attack(0, &enemy), 3750);
+        character.bravery = true;
+        assert_eq!(character.attack(0, &enemy), 4875);
+        character.weapon.attack = 200;
+        assert_eq!(character.attack(0, &enemy), damage::DAMAGE_CAP);
+        character.berserk = false;
+        character.bravery = false;
+
+        // Poles hit against magick defense
+        character.weapon = damage::Weapon::new(damage::WeaponType::Pole, 60);
+        assert!(character.attack(0, &enemy::Enemy::new(255, 0)) > 0);
+        assert_eq!(character.attack(0, &enemy::Enemy::new(0, 255)), 0);
+
+        // Axes roll 0~1.111 rather than 1~1.125
+        assert_eq!(damage::WeaponType::Axe.variance(0), 0.0);
+        assert!((damage::WeaponType::Axe.variance(u32::MAX) - 1.111).abs() < 1e-6);
+        assert_eq!(damage::WeaponType::Sword.variance(0), 1.0);
+        assert!((damage::WeaponType::Sword.variance(u32::MAX) - 1.125).abs() < 1e-6);
+        character.weapon = damage::Weapon::new(damage::WeaponType::Axe, 60);
+        assert_eq!(character.attack(0, &enemy::Enemy::new(0, 0)), 0);
+
+        // Brawler swaps the unarmed attack for (level + strength) / 2
+        character.weapon = damage::Weapon::default();
+        let enemy = enemy::Enemy::new(10, 0);
+        let multiplier = damage::WeaponType::Unarmed.multiplier(character.level, character.strength, character.magic, character.vitality, character.speed);
+        assert_eq!(character.attack(0, &enemy), multiplier as i32);
+        character.brawler = true;
+        assert_eq!(character.attack(0, &enemy), (45.0 * multiplier) as i32);
+    }
+
+    #[test]
+    fn crits_and_combos() {
+        let mut character = character::Character { weapon: damage::Weapon::new(damage::WeaponType::Spear, 60), ..Default::default() };
+        assert!(character.crits(104));
+        assert!(!character.crits(5));
+        assert!(!character.combos(0));
+        character.weapon.kind = damage::WeaponType::Sword;
+        assert!(!character.crits(0));
+        character.weapon.kind = damage::WeaponType::Katana;
+        assert!(!character.combos(12));
+        character.genji_gloves = true;
+        assert!(character.combos(12));
+
+        character.weapon.kind = damage::WeaponType::Spear;
+        let rng_helper = rng_helper::RNGHelper::new(Some(1), &character, 200);
+        let position = rng_helper.position();
+        let distance = rng_helper.next_crit(position).unwrap();
+        assert!(rng_helper.get(position + distance).unwrap().crit);
+        assert!((1..distance).all(|offset| !rng_helper.get(position + offset).unwrap().crit));
+
+        // Each attack takes a hit and a damage draw, so combo rolls are two rows apart
+        character.weapon.kind = damage::WeaponType::NinjaSword;
+        let rng_helper = rng_helper::RNGHelper::new(Some(1), &character, 200);
+        let position = rng_helper.position();
+        let chain = rng_helper.combo_chain(position);
+        assert!((0..chain).all(|hit| rng_helper.get(position + 1 + hit * 2).unwrap().combo));
+        assert!(!rng_helper.get(position + 1 + chain * 2).unwrap().combo);
+    }
+
+    #[test]
+    fn status_chances() {
+        let mut enemy = enemy::Enemy::new(20, 20);
+        assert_eq!(enemy.status_chance(status::StatusAction::Sleep), 45);
+        enemy.status_resistance = 20;
+        assert_eq!(enemy.status_chance(status::StatusAction::Sleep), 25);
+        enemy.status_resistance = 50;
+        assert_eq!(enemy.status_chance(status::StatusAction::Sleep), 0);
+        enemy.status_resistance = 20;
+
+        let mut character = character::Character::default();
+        assert!(!character.inflicts(0, &enemy));
+        character.status_action = Some(status::StatusAction::Sleep);
+        assert!(character.inflicts(124, &enemy));
+        assert!(!character.inflicts(25, &enemy));
+        enemy.set_immunity(status::Status::Sleep, true);
+        assert_eq!(enemy.status_chance(status::StatusAction::Sleepga), 0);
+        assert!(!character.inflicts(0, &enemy));
+        assert_eq!(enemy.status_chance(status::StatusAction::Blind), 25);
+    }
+
+    #[test]
+    fn hit_evasion() {
+        let evasion = evasion::Evasion::new(10, 10, 10);
+        assert_eq!(evasion.check(105, false), evasion::HitOutcome::Blocked);
+        assert_eq!(evasion.check(15, false), evasion::HitOutcome::Parried);
+        assert_eq!(evasion.check(25, false), evasion::HitOutcome::Evaded);
+        assert_eq!(evasion.check(35, false), evasion::HitOutcome::Hit);
+        // Blind attackers double every chance
+        assert_eq!(evasion.check(15, true), evasion::HitOutcome::Blocked);
+        assert_eq!(evasion.check(35, true), evasion::HitOutcome::Parried);
+        assert_eq!(evasion.check(55, true), evasion::HitOutcome::Evaded);
+        assert_eq!(evasion.check(65, true), evasion::HitOutcome::Hit);
+
+        let mut character = character::Character::default();
+        let mut enemy = enemy::Enemy::new(20, 20);
+        enemy.evasion = evasion;
+        assert_eq!(character.hits(35, &enemy), evasion::HitOutcome::Hit);
+        character.blind = true;
+        assert_eq!(character.hits(35, &enemy), evasion::HitOutcome::Parried);
+        // Guns can't be avoided, even blind
+        character.weapon = damage::Weapon::new(damage::WeaponType::Gun, 50);
+        assert_eq!(character.hits(5, &enemy), evasion::HitOutcome::Hit);
+
+        character.evasion = evasion;
+        assert_eq!(character.defends(35, &enemy), evasion::HitOutcome::Hit);
+        enemy.blind = true;
+        assert_eq!(character.defends(35, &enemy), evasion::HitOutcome::Parried);
+    }
+
+    #[test]
+    fn magick_affinity() {
+        let character = character::Character {
+            spell: spell::Spell::Fire,
+            ..Default::default()
+        };
+        let mut enemy = enemy::Enemy::new(0, 10);
+        let normal = character.cast(12_345, &enemy);
+        enemy.set_affinity(spell::Element::Fire, enemy::Affinity::Weak);
+        let weak = character.cast(12_345, &enemy);
+        enemy.set_affinity(spell::Element::Fire, enemy::Affinity::Absorb);
+        let absorbed = character.cast(12_345, &enemy);
+        assert!(normal > 0);
+        assert!(weak >= normal * 2 - 1 && weak <= normal * 2 + 1);
+        assert!(absorbed < 0);
+
+        let dark = character::Character { spell: spell::Spell::Darkga, ..character };
+        let enemy = enemy::Enemy::new(0, 10);
+        let mut weak = enemy;
+        weak.set_affinity(spell::Element::Dark, enemy::Affinity::Weak);
+        assert_eq!(dark.spell.element(), Some(spell::Element::Dark));
+        assert!(dark.cast(12_345, &weak) > dark.cast(12_345, &enemy));
+    }
+
+    #[test]
+    fn healing_model() {
+        let enemy = enemy::Enemy::default();
+        let plain = character::Character::new(50, 50, spell::Spell::Cure, false);
+        let faith = character::Character { faith: true, ..plain };
+        let lore = character::Character { magick_lore: 10, ..plain };
+        assert!(faith.cast(12_345, &enemy) > plain.cast(12_345, &enemy));
+        assert!(lore.cast(12_345, &enemy) > plain.cast(12_345, &enemy));
+        let renew = character::Character { spell: spell::Spell::Renew, ..plain };
+        assert_eq!(renew.cast(12_345, &enemy), damage::DAMAGE_CAP);
+
+        // Curaga on three targets splits the heal, and takes a draw for each of them
+        let curaga = character::Character { spell: spell::Spell::Curaga, ..plain };
+        let split = character::Character { targets: 3, ..curaga };
+        assert!((curaga.cast(12_345, &enemy) / 3 - split.cast(12_345, &enemy)).abs() <= 1);
+        let reference = rng_helper::RNGHelper::new(Some(1_234), &split, 20);
+        let values: Vec<i32> = vec![reference.values[4].spell, reference.values[7].spell, reference.values[10].spell];
+        let mut rng_helper = rng_helper::RNGHelper::new(Some(1_234), &split, 1);
+        assert!(rng_helper.find_casts(&split, &values, Some(100)));
+        assert_eq!(rng_helper.position(), 4);
+
+        // Heals cap like everything else
+        let curaja = character::Character::new(70, 99, spell::Spell::Curaja, true);
+        assert_eq!(curaja.cast(12_345, &enemy), damage::DAMAGE_CAP);
+
+        // Cures hurt undead, split between targets like a heal, but Curaja and Renew only ever land on allies
+        let armoured = enemy::Enemy::new(0, 20);
+        let undead = character::Character { undead_target: true, ..curaga };
+        assert!(undead.cast(12_345, &armoured) < curaga.cast(12_345, &armoured));
+        let undead_split = character::Character { targets: 3, ..undead };
+        assert!((undead.cast(12_345, &armoured) / 3 - undead_split.cast(12_345, &armoured)).abs() <= 1);
+        let curaja = character::Character { spell: spell::Spell::Curaja, ..plain };
+        assert_eq!(character::Character { undead_target: true, ..curaja }.cast(12_345, &enemy), curaja.cast(12_345, &enemy));
+        assert_eq!(character::Character { undead_target: true, ..renew }.cast(12_345, &enemy), damage::DAMAGE_CAP);
+    }
+
+    #[test]
+    fn game_versions() {
+        let enemy = enemy::Enemy::default();
+        let tza = character::Character::new(30, 30, spell::Spell::Holy, false);
+        let vanilla = character::Character { version: version::GameVersion::Vanilla, ..tza };
+        assert!(vanilla.cast(12_345, &enemy) < tza.cast(12_345, &enemy));
+        assert_ne!(
+            seeding::SeedingModel::for_version(version::GameVersion::IZJS),
+            seeding::SeedingModel::for_version(version::GameVersion::TZAConsole)
+        );
+        assert_eq!(seeding::SeedingModel::for_version(version::GameVersion::Vanilla), seeding::SeedingModel::Fixed(rng::RNG::DEFAULT_SEED));
+    }
+
+    #[test]
+    fn find_potion_heals() {
+        let character = character::Character {
+            item: Some(item::Item::HiPotion),
+            potion_lore: 2,
+            ..Default::default()
+        };
+        let reference = rng_helper::RNGHelper::new(Some(1_234), &character, 20);
+        let values: Vec<i32> = reference.values[10..14].iter().map(|v| v.spell).collect();
+        let mut rng_helper = rng_helper::RNGHelper::new(Some(1_234), &character, 10);
+        assert!(rng_helper.find_casts(&character, &values, Some(100)));
+        assert_eq!(rng_helper.values[0].position, 11);
+    }
+
+    #[test]
+    fn find_mixed_observations() {
+        let character = character::Character::default();
+        let enemy = enemy::Enemy::new(10, 10);
+        let reference = rng_helper::RNGHelper::new(Some(1_234), &character, 20);
+        let raw: Vec<u32> = reference.values.iter().map(|v| v.value).collect();
+        let potion = observation::ItemHeal {
+            item: item::Item::Potion,
+            potion_lore: 0,
+            value: item::Item::Potion.heal(raw[13], 0),
+        };
+        let observations: Vec<Box<dyn observation::Observation>> = vec![
+            Box::new(observation::Cast {
+                character,
+                enemy,
+                value: character.cast(raw[10], &enemy),
+            }),
+            Box::new(observation::Attack {
+                character,
+                enemy,
+                value: character.attack(raw[12], &enemy),
+            }),
+            Box::new(potion),
+            Box::new(observation::Chest {
+                chance: 50,
+                appeared: raw[14] % 100 < 50,
+            }),
+        ];
+        let mut rng_helper = rng_helper::RNGHelper::new(Some(1_234), &character, 1);
+        assert!(rng_helper.find_observations(&character, &observations, Some(100)));
+        assert_eq!(rng_helper.values[0].position, 11);
+    }
+
+    #[test]
+    fn advance_by_actions() {
+        use action::Action;
+        let character = character::Character::default();
+        let mut rng_helper = rng_helper::RNGHelper::new(Some(1_234), &character, 50);
+        let steal = rng_helper.steal(4, false).unwrap();
+        let actions = [Action::Cast, Action::Cast, Action::Menu, Action::OpenChest, Action::Steal(steal)];
+        let target = Action::position_after(rng_helper.position(), &actions);
+        let value = rng_helper.get(target + 1).unwrap().value;
+        rng_helper.advance(&character, &actions);
+        assert_eq!(rng_helper.position(), 4 + steal.draws() as u32);
+        assert_eq!(rng_helper.values[0].value, value);
+
+        // An attack's damage comes from the draw after its hit roll
+        let enemy = enemy::Enemy::default();
+        assert_eq!(rng_helper.values[0].attack, character.attack(rng_helper.values[1].value, &enemy));
+        rng_helper.advance(&character, &[Action::Attack]);
+        assert_eq!(rng_helper.position(), target + 2);
+        let last = rng_helper.values.last().unwrap();
+        let mut rng = rng::RNG::from(1_234);
+        let variance = (0..=last.position).map(|_| rng.gen_rand()).last().unwrap();
+        assert_eq!(last.attack, character.attack(variance, &enemy));
+    }
+
+    #[test]
+    fn plan_actions() {
+        use action::Action;
+        let actions = [Action::Cast, Action::OpenChest, Action::ZoneTransition(5)];
+        let steps = planner::plan(10, 17, &actions).unwrap();
+        assert_eq!(
+            planner::describe(&steps),
+            "Open chest, then Change zone".to_string()
+        );
+        assert_eq!(planner::plan(10, 9, &actions), None);
+        assert_eq!(planner::plan(0, 3, &[Action::OpenChest]), None);
+
+        // Far off targets are worked out without stepping through every draw
+        let steps = planner::plan(0, u32::MAX, &Action::TABLE).unwrap();
+        assert_eq!(steps, vec![
+            planner::Step { action: Action::Cast, count: 1 },
+            planner::Step { action: Action::Attack, count: u32::MAX / 2 },
+        ]);
+    }
+
+    #[test]
+    fn find_party_casts() {
+        let healer = character::Character::default();
+        let backup = character::Character::new(30, 40, spell::Spell::Cura, false);
+        let party = party::Party::new(vec![healer, backup]);
+        let mut reference = rng_helper::RNGHelper::new(Some(1_234), &healer, 0);
+        reference.apply_party(&party);
+        for _ in 0..20 {
+            reference.push(&healer);
+        }
+        let casts: Vec<(usize, i32)> = vec![
+            (0, reference.values[8].party[0]),
+            (1, reference.values[9].party[1]),
+            (0, reference.values[10].party[0]),
+        ];
+        let mut rng_helper = rng_helper::RNGHelper::new(Some(1_234), &healer, 1);
+        rng_helper.apply_party(&party);
+        assert!(rng_helper.find_party_casts(&healer, &casts, Some(100)));
+        assert_eq!(rng_helper.values[0].position, 9);
+    }
+
+    #[test]
+    fn resume_search() {
+        let character = character::Character::default();
+        let values: Vec<i32> = vec![2255, 2063, 2029, 2211, 2195];
+        let mut job = search::SearchJob::new(&character, &enemy::Enemy::default(), &values, 6_200_000, 6_400_000, 1_000);
+        assert!(job.step().is_none());
+        assert_eq!(job.searched(), search::SearchJob::CHUNK as u64);
+
+        let mut resumed = search::SearchJob::from_json(&job.to_json().unwrap()).unwrap();
+        let rng_helper = resumed.run(|_| {}).unwrap();
+        assert_eq!(rng_helper.rng.seed, 6_357_987);
+        assert_eq!(resumed.candidates, vec![6_357_987]);
+    }
+
+    #[test]
+    fn sharded_search() {
+        let character = character::Character::default();
+        let enemy = enemy::Enemy::default();
+        let values: Vec<i32> = vec![2255, 2063, 2029, 2211, 2195];
+        let units = shard::shards(character.version, 6_340_000, 6_370_000, 5_000);
+        assert_eq!(units.len(), 6);
+        let job = shard::ShardJob { character, enemy, values: values.clone(), iters: 1_000 };
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        // The first worker takes the first unit and goes quiet without hanging up, and the next drops out,
+        // so the others have to pick both units back up
+        let (release, held) = std::sync::mpsc::channel::<()>();
+        let stalled = std::thread::spawn(move || {
+            use std::io::BufRead;
+            let stream = std::net::TcpStream::connect(addr).unwrap();
+            let mut request = String::new();
+            std::io::BufReader::new(&stream).read_line(&mut request).unwrap();
+            let _ = held.recv();
+        });
+        let dropped = std::thread::spawn(move || {
+            use std::io::BufRead;
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            let stream = std::net::TcpStream::connect(addr).unwrap();
+            let mut request = String::new();
+            std::io::BufReader::new(&stream).read_line(&mut request).unwrap();
+            std::thread::sleep(std::time::Duration::from_secs(1));
+        });
+        let workers: Vec<_> = (0..2).map(|_| std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(200));
+            shard::work(addr)
+        })).collect();
+        let coordinator = shard::Coordinator::new(job, units).timeout(std::time::Duration::from_secs(30)).worker_timeout(std::time::Duration::from_secs(3));
+        let sharded = coordinator.serve(listener).unwrap().unwrap();
+        drop(release);
+        stalled.join().unwrap();
+        dropped.join().unwrap();
+        // A worker which connects after the last unit is done finds nobody listening, which is fine
+        for worker in workers {
+            let _ = worker.join();
+        }
+
+        let single = rng_helper::RNGHelper::find_seed(&character, &enemy, &values, 6_340_000, 6_370_000, 1_000).unwrap();
+        assert_eq!(sharded.rng.seed, single.rng.seed);
+        assert_eq!(sharded.values[0].position, single.values[0].position);
+    }
+
+    #[test]
+    fn seed_index_lookup() {
+        let path = std::env::temp_dir().join("ffxii_tza_rng_seed_index_lookup.idx");
+        let modulus = character::Character::default().heal_modulus().unwrap();
+        index::build(&path, modulus, 1_000, 1_500, 3, 8).unwrap();
+
+        // Stats differ from the character the modulus came from, but the index still applies
+        let character = character::Character::new(62, 55, spell::Spell::Cure, true);
+        let reference = rng_helper::RNGHelper::new(Some(1_234), &character, 10);
+        let heals: Vec<i32> = reference.values[5..9].iter().map(|lens| lens.spell).collect();
+        let mut seed_index = index::SeedIndex::open(&path).unwrap();
+        let candidates = seed_index.lookup(&character, &heals).unwrap();
+        assert!(candidates.contains(&index::Candidate { seed: 1_234, offset: 5 }));
+        let rng_helper = candidates[0].helper(&character, 4);
+        assert_eq!(rng_helper.values[0].spell, heals[0]);
+
+        // Early game heals match dozens of residues each, which narrowing a heal at a time copes with
+        let weak = character::Character::new(5, 10, spell::Spell::Cure, false);
+        assert!(weak.heal_residues(weak.cast(125, &enemy::Enemy::default())).len() > 30);
+        let reference = rng_helper::RNGHelper::new(Some(1_234), &weak, 10);
+        let heals: Vec<i32> = reference.values[2..7].iter().map(|lens| lens.spell).collect();
+        let candidates = seed_index.lookup(&weak, &heals).unwrap();
+        assert!(candidates.contains(&index::Candidate { seed: 1_234, offset: 2 }));
+        assert!(seed_index.lookup(&weak, &heals[..2]).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn session_round_trip() {
+        let character = character::Character::default();
+        let mut rng_helper = rng_helper::RNGHelper::new(Some(6_357_987), &character, 20);
+        rng_helper.advance(&character, &[action::Action::Cast, action::Action::Attack]);
+        let session = session::Session::capture(&character, &rng_helper, &[(0, 2255)], &[40]);
+
+        let restored = session::Session::from_json(&session.to_json().unwrap()).unwrap();
+        let restored_helper = restored.restore(20);
+        assert_eq!(restored_helper.position(), 3);
+        assert_eq!(restored_helper.values[0].value, rng_helper.values[0].value);
+        assert_eq!(restored.targets, vec![40]);
+
+        // Sessions from before the format was versioned still load
+        let unversioned = session::Session::from_json(r#"{"seed": 1234, "position": 5}"#).unwrap();
+        assert_eq!(unversioned.version, session::Session::VERSION);
+        assert_eq!(unversioned.restore(1).position(), 5);
+
+        // Version 1 sessions kept the game version on its own
+        let v1 = session::Session::from_json(r#"{"version": 1, "game": "Vanilla", "seed": 1234}"#).unwrap();
+        assert_eq!(v1.character.version, version::GameVersion::Vanilla);
+
+        // Version 2 enemies had an Earth affinity ahead of Dark
+        let mut v2 = serde_json::to_value(&session).unwrap();
+        v2["version"] = serde_json::json!(2);
+        v2["enemy"]["affinities"] = serde_json::json!(["Normal", "Normal", "Normal", "Normal", "Normal", "Absorb", "Weak", "Normal"]);
+        let v2 = session::Session::from_json(&v2.to_string()).unwrap();
+        assert_eq!(v2.enemy.affinity(spell::Element::Dark), enemy::Affinity::Weak);
+        assert_eq!(v2.enemy.affinity(spell::Element::Holy), enemy::Affinity::Normal);
+    }
+
+    #[test]
+    fn export_rows() {
+        let character = character::Character::default();
+        let mut rng_helper = rng_helper::RNGHelper::new(Some(4_537), &character, 3);
+        let backup = character::Character::new(30, 40, spell::Spell::Cura, false);
+        rng_helper.apply_party(&party::Party::new(vec![character, backup]));
+        // The first heal follows the character being edited, even before the party catches up
+        rng_helper.apply_character(&character::Character { faith: true, ..character });
+
+        let csv = export::Format::Csv.export(&rng_helper.values);
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("Position,Value,Heal 1,Heal 2,Chest"));
+        let first = &rng_helper.values[0];
+        assert_eq!(
+            lines.next().unwrap(),
+            format!("{},{},{},{},{}", first.position, first.value, first.spell, first.party[1], first.chest)
+        );
+
+        let json = export::Format::JsonLines.export(&rng_helper.values);
+        let row: export::ExportRow = serde_json::from_str(json.lines().next().unwrap()).unwrap();
+        assert_eq!(row, export::ExportRow::from(first));
+
+        let markdown = export::Format::Markdown.export(&rng_helper.values);
+        assert_eq!(markdown.lines().count(), 5);
+        assert!(markdown.lines().all(|line| line.starts_with('|') && line.ends_with('|')));
+    }
 }
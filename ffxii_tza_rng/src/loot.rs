@@ -0,0 +1,215 @@
+use serde_derive::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum DropSlot {
+    VeryRare,
+    Rare,
+    Uncommon,
+    Common,
+}
+
+impl DropSlot {
+    pub fn name(&self) -> &str {
+        match *self {
+            DropSlot::VeryRare => "Very Rare",
+            DropSlot::Rare => "Rare",
+            DropSlot::Uncommon => "Uncommon",
+            DropSlot::Common => "Common",
+        }
+    }
+}
+
+/// The chain level, which decides the drop and slot chances
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum ChainLevel {
+    One,
+    Two,
+    Three,
+    Four,
+}
+
+impl ChainLevel {
+    pub fn from_chain(chain: u32) -> ChainLevel {
+        match chain {
+            0..=9 => ChainLevel::One,
+            10..=19 => ChainLevel::Two,
+            20..=34 => ChainLevel::Three,
+            _ => ChainLevel::Four,
+        }
+    }
+
+    /// Chance out of 100 for the enemy to drop anything
+    pub fn drop_chance(&self) -> u32 {
+        match *self {
+            ChainLevel::One => 60,
+            ChainLevel::Two => 70,
+            ChainLevel::Three => 80,
+            ChainLevel::Four => 90,
+        }
+    }
+
+    /// Chance out of 100 for each slot, rarest first. Common takes the remainder
+    fn slot_chances(&self) -> [(DropSlot, u32); 3] {
+        let (very_rare, rare, uncommon) = match *self {
+            ChainLevel::One => (1, 5, 25),
+            ChainLevel::Two => (2, 8, 30),
+            ChainLevel::Three => (3, 10, 33),
+            ChainLevel::Four => (5, 15, 35),
+        };
+        [
+            (DropSlot::VeryRare, very_rare),
+            (DropSlot::Rare, rare),
+            (DropSlot::Uncommon, uncommon),
+        ]
+    }
+
+    /// Chance out of 100 for the Monograph item to drop
+    pub fn monograph_chance(&self, canopic_jar: bool) -> u32 {
+        if !canopic_jar {
+            return 5;
+        }
+        match *self {
+            ChainLevel::One => 10,
+            ChainLevel::Two => 20,
+            ChainLevel::Three => 40,
+            ChainLevel::Four => 60,
+        }
+    }
+}
+
+/// Items an enemy can drop, per slot, with the item its Monograph adds
+#[derive(Debug, Copy, Clone)]
+pub struct DropTable {
+    pub name: &'static str,
+    pub common: &'static str,
+    pub uncommon: &'static str,
+    pub rare: &'static str,
+    pub very_rare: &'static str,
+    pub monograph: &'static str,
+}
+
+impl DropTable {
+    pub fn item(&self, slot: DropSlot) -> &'static str {
+        match slot {
+            DropSlot::VeryRare => self.very_rare,
+            DropSlot::Rare => self.rare,
+            DropSlot::Uncommon => self.uncommon,
+            DropSlot::Common => self.common,
+        }
+    }
+}
+
+impl FromStr for DropTable {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        DROP_TABLES
+            .iter()
+            .find(|table| table.name == s)
+            .copied()
+            .ok_or(())
+    }
+}
+
+/// Everything which affects the drop rolls besides the enemy itself
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone)]
+pub struct LootModifiers {
+    pub chain: u32,
+    pub monograph: bool,
+    pub canopic_jar: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct Loot {
+    pub slot: Option<DropSlot>,
+    pub monograph: bool,
+}
+
+impl Loot {
+    /// The names of the items dropped from the given table
+    pub fn items(&self, table: &DropTable) -> Vec<&'static str> {
+        let mut items = vec![];
+        if let Some(slot) = self.slot {
+            items.push(table.item(slot));
+        }
+        if self.monograph {
+            items.push(table.monograph);
+        }
+        items
+    }
+}
+
+/// Works out what an enemy drops from the upcoming RNG values
+///
+/// The game rolls whether anything drops, then which slot, then the Monograph item if one is held.
+/// Returns `None` if there aren't enough values to decide the outcome
+pub fn loot(rng_vals: &[u32], modifiers: &LootModifiers) -> Option<Loot> {
+    let level = ChainLevel::from_chain(modifiers.chain);
+    let mut rng_vals = rng_vals.iter();
+
+    let slot = if rng_vals.next()? % 100 < level.drop_chance() {
+        let roll = rng_vals.next()? % 100;
+        let mut threshold = 0;
+        let slot = level.slot_chances().iter().find_map(|(slot, chance)| {
+            threshold += chance;
+            if roll < threshold {
+                Some(*slot)
+            } else {
+                None
+            }
+        });
+        Some(slot.unwrap_or(DropSlot::Common))
+    } else {
+        None
+    };
+
+    let monograph = modifiers.monograph
+        && rng_vals.next()? % 100 < level.monograph_chance(modifiers.canopic_jar);
+
+    Some(Loot { slot, monograph })
+}
+
+/// Drop tables for some commonly farmed enemies
+pub const DROP_TABLES: [DropTable; 5] = [
+    DropTable {
+        name: "Wolf",
+        common: "Wolf Pelt",
+        uncommon: "Fire Stone",
+        rare: "Potion",
+        very_rare: "Knot of Rust",
+        monograph: "Ring Wyrm Scale",
+    },
+    DropTable {
+        name: "Cactoid",
+        common: "Cactus Fruit",
+        uncommon: "Earth Stone",
+        rare: "Eye Drops",
+        very_rare: "Knot of Rust",
+        monograph: "Cactus Flower",
+    },
+    DropTable {
+        name: "Skeleton",
+        common: "Bone Fragment",
+        uncommon: "Dark Stone",
+        rare: "Phoenix Down",
+        very_rare: "Knot of Rust",
+        monograph: "Sturdy Bone",
+    },
+    DropTable {
+        name: "Coeurl",
+        common: "Coeurl Pelt",
+        uncommon: "Coeurl Whisker",
+        rare: "Hi-Potion",
+        very_rare: "Knot of Rust",
+        monograph: "Prime Pelt",
+    },
+    DropTable {
+        name: "Dragon Aevis",
+        common: "Wyvern Wing",
+        uncommon: "Aevis Killer",
+        rare: "Hi-Potion",
+        very_rare: "Knot of Rust",
+        monograph: "Wyrm Bone",
+    },
+];
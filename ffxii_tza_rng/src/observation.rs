@@ -0,0 +1,131 @@
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{character, enemy, evasion, item};
+
+/// Something seen in game which is decided by the RNG, and so can be used to find the seed
+pub trait Observation: Sync {
+    /// How many RNG values the observation consumes
+    fn draws(&self) -> usize;
+
+    /// The outcome the given RNG values would produce
+    fn outcome(&self, rng_vals: &[u32]) -> i32;
+
+    /// The outcome that was actually seen
+    fn observed(&self) -> i32;
+
+    fn matches(&self, rng_vals: &[u32]) -> bool {
+        rng_vals.len() >= self.draws() && self.outcome(rng_vals) == self.observed()
+    }
+}
+
+/// Checks a sequence of observations against the RNG values they were made from, in order
+pub fn matches_all(observations: &[Box<dyn Observation>], rng_vals: &[u32]) -> bool {
+    let mut offset = 0;
+    for observation in observations {
+        if offset > rng_vals.len() || !observation.matches(&rng_vals[offset..]) {
+            return false;
+        }
+        offset += observation.draws();
+    }
+    true
+}
+
+/// How many RNG values a sequence of observations consumes
+pub fn total_draws(observations: &[Box<dyn Observation>]) -> usize {
+    observations
+        .iter()
+        .map(|observation| observation.draws())
+        .sum()
+}
+
+/// A spell cast: HP restored for cures, or damage done for offensive spells
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub struct Cast {
+    pub character: character::Character,
+    pub enemy: enemy::Enemy,
+    pub value: i32,
+}
+
+impl Observation for Cast {
+    /// A multi-target cast takes a draw for every target, `value` being the first target's heal
+    fn draws(&self) -> usize {
+        self.character.targets()
+    }
+
+    fn outcome(&self, rng_vals: &[u32]) -> i32 {
+        self.character.cast(rng_vals[0], &self.enemy)
+    }
+
+    fn observed(&self) -> i32 {
+        self.value
+    }
+}
+
+/// HP restored by a healing item
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub struct ItemHeal {
+    pub item: item::Item,
+    pub potion_lore: u8,
+    pub value: i32,
+}
+
+impl Observation for ItemHeal {
+    fn draws(&self) -> usize {
+        1
+    }
+
+    fn outcome(&self, rng_vals: &[u32]) -> i32 {
+        self.item.heal(rng_vals[0], self.potion_lore)
+    }
+
+    fn observed(&self) -> i32 {
+        self.value
+    }
+}
+
+/// Damage done by a physical attack which landed
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub struct Attack {
+    pub character: character::Character,
+    pub enemy: enemy::Enemy,
+    pub value: i32,
+}
+
+impl Observation for Attack {
+    /// The hit roll, then the damage variance
+    fn draws(&self) -> usize {
+        2
+    }
+
+    fn outcome(&self, rng_vals: &[u32]) -> i32 {
+        match self.character.hits(rng_vals[0], &self.enemy) {
+            evasion::HitOutcome::Hit => self.character.attack(rng_vals[1], &self.enemy),
+            _ => 0,
+        }
+    }
+
+    fn observed(&self) -> i32 {
+        self.value
+    }
+}
+
+/// Whether a chest with a `chance` out of 100 appeared
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub struct Chest {
+    pub chance: u8,
+    pub appeared: bool,
+}
+
+impl Observation for Chest {
+    fn draws(&self) -> usize {
+        1
+    }
+
+    fn outcome(&self, rng_vals: &[u32]) -> i32 {
+        (rng_vals[0] % 100 < self.chance as u32) as i32
+    }
+
+    fn observed(&self) -> i32 {
+        self.appeared as i32
+    }
+}
@@ -0,0 +1,71 @@
+use serde_derive::{Deserialize, Serialize};
+
+use crate::observation::Observation;
+use crate::{character, enemy, observation};
+
+/// The party of characters whose heals are tracked together
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Party {
+    pub members: Vec<character::Character>,
+}
+
+impl Party {
+    pub const MAX_MEMBERS: usize = 3;
+
+    /// Creates a party, keeping at most `MAX_MEMBERS` of the given characters
+    pub fn new(mut members: Vec<character::Character>) -> Party {
+        members.truncate(Party::MAX_MEMBERS);
+        Party { members }
+    }
+
+    /// Adds a member, returning false if the party is already full
+    pub fn add(&mut self, character: character::Character) -> bool {
+        if self.members.len() >= Party::MAX_MEMBERS {
+            return false;
+        }
+        self.members.push(character);
+        true
+    }
+
+    pub fn remove(&mut self, idx: usize) {
+        if idx < self.members.len() {
+            self.members.remove(idx);
+        }
+    }
+
+    /// Every member's outcome for the given RNG value, in party order
+    pub fn heals(&self, rng_val: u32, enemy: &enemy::Enemy) -> Vec<i32> {
+        self.members
+            .iter()
+            .map(|member| member.outcome(rng_val, enemy))
+            .collect()
+    }
+
+    /// Turns heals cast by members, as `(member, value)` pairs in order, into observations.
+    /// Returns `None` if any member isn't in the party
+    pub fn observations(
+        &self,
+        casts: &[(usize, i32)],
+        enemy: &enemy::Enemy,
+    ) -> Option<Vec<Box<dyn Observation>>> {
+        casts
+            .iter()
+            .map(|(idx, value)| {
+                let member = self.members.get(*idx)?;
+                let observation: Box<dyn Observation> = match member.item {
+                    Some(item) => Box::new(observation::ItemHeal {
+                        item,
+                        potion_lore: member.potion_lore,
+                        value: *value,
+                    }),
+                    None => Box::new(observation::Cast {
+                        character: *member,
+                        enemy: *enemy,
+                        value: *value,
+                    }),
+                };
+                Some(observation)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,119 @@
+use serde_derive::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::action;
+
+/// One line of a plan: take `action`, `count` times
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct Step {
+    pub action: action::Action,
+    pub count: u32,
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.count == 1 {
+            write!(f, "{}", self.action)
+        } else {
+            write!(f, "{} x{}", self.action, self.count)
+        }
+    }
+}
+
+/// The most draws `plan` works through one at a time
+const MAX_SPAN: u64 = 1_000_000;
+
+/// Finds the fewest actions which move the RNG from `from` to exactly `to`
+///
+/// Returns `None` if `to` is behind `from`, no mix of `actions` lands on it,
+/// or the biggest action takes so many draws that planning would take too long
+pub fn plan(from: u32, to: u32, actions: &[action::Action]) -> Option<Vec<Step>> {
+    if to < from {
+        return None;
+    }
+    let distance = (to - from) as u64;
+    if distance == 0 {
+        return Some(vec![]);
+    }
+    let mut largest: Option<(usize, u64)> = None;
+    for (idx, action) in actions.iter().enumerate() {
+        let draws = action.draws() as u64;
+        if draws > largest.map_or(0, |(_, most)| most) {
+            largest = Some((idx, draws));
+        }
+    }
+    let (largest, most) = largest?;
+
+    // Any `most` of the smaller actions include some whose draws add up to a multiple of `most`,
+    // which fewer of the largest action could replace. So the smaller actions in the best plan
+    // cover fewer than `most²` draws, and everything beyond that can go on the largest action
+    let bound = most * most;
+    let bulk = if distance > bound {
+        (distance - bound) / most
+    } else {
+        0
+    };
+    let remainder = distance - bulk * most;
+    if remainder > MAX_SPAN {
+        return None;
+    }
+    let mut counts = fewest(remainder as usize, actions)?;
+    counts[largest] += bulk as u32;
+
+    Some(
+        actions
+            .iter()
+            .zip(counts)
+            .filter(|(_, count)| *count > 0)
+            .map(|(action, count)| Step {
+                action: *action,
+                count,
+            })
+            .collect(),
+    )
+}
+
+/// How many of each action make up the fewest actions covering exactly `distance` draws
+fn fewest(distance: usize, actions: &[action::Action]) -> Option<Vec<u32>> {
+    // fewest[d] is the fewest actions covering d draws, and last[d] the action that finishes it
+    let mut fewest: Vec<Option<u32>> = vec![None; distance + 1];
+    let mut last: Vec<usize> = vec![0; distance + 1];
+    fewest[0] = Some(0);
+    for d in 1..=distance {
+        for (idx, action) in actions.iter().enumerate() {
+            let draws = action.draws() as usize;
+            if draws == 0 || draws > d {
+                continue;
+            }
+            match (fewest[d - draws], fewest[d]) {
+                (Some(count), Some(best)) if count + 1 >= best => {}
+                (Some(count), _) => {
+                    fewest[d] = Some(count + 1);
+                    last[d] = idx;
+                }
+                (None, _) => {}
+            }
+        }
+    }
+    fewest[distance]?;
+
+    let mut counts = vec![0; actions.len()];
+    let mut d = distance;
+    while d > 0 {
+        counts[last[d]] += 1;
+        d -= actions[last[d]].draws() as usize;
+    }
+    Some(counts)
+}
+
+/// Writes out a plan as a single readable line
+pub fn describe(steps: &[Step]) -> String {
+    if steps.is_empty() {
+        return "Already there".to_string();
+    }
+    steps
+        .iter()
+        .map(|step| step.to_string())
+        .collect::<Vec<String>>()
+        .join(", then ")
+}
@@ -0,0 +1,104 @@
+use serde_derive::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum PoachOutcome {
+    Rare,
+    Common,
+    Failed,
+}
+
+impl PoachOutcome {
+    pub fn name(&self) -> &str {
+        match *self {
+            PoachOutcome::Rare => "Rare",
+            PoachOutcome::Common => "Common",
+            PoachOutcome::Failed => "Failed",
+        }
+    }
+}
+
+/// Items an enemy can be poached for
+///
+/// Poach only works on an enemy under 25% HP; these odds apply once that holds
+#[derive(Debug, Copy, Clone)]
+pub struct PoachTable {
+    pub name: &'static str,
+    pub common: &'static str,
+    pub rare: &'static str,
+    pub chance: u8,
+    pub rare_chance: u8,
+}
+
+impl PoachTable {
+    /// Works out the result of a poach for the given RNG value
+    pub fn poach(&self, rng_val: u32) -> PoachOutcome {
+        let roll = rng_val % 100;
+        if roll >= self.chance as u32 {
+            PoachOutcome::Failed
+        } else if roll < self.rare_chance as u32 {
+            PoachOutcome::Rare
+        } else {
+            PoachOutcome::Common
+        }
+    }
+
+    pub fn item(&self, outcome: PoachOutcome) -> Option<&'static str> {
+        match outcome {
+            PoachOutcome::Rare => Some(self.rare),
+            PoachOutcome::Common => Some(self.common),
+            PoachOutcome::Failed => None,
+        }
+    }
+}
+
+impl FromStr for PoachTable {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        POACH_TABLES
+            .iter()
+            .find(|table| table.name == s)
+            .copied()
+            .ok_or(())
+    }
+}
+
+/// Poach tables for some commonly poached enemies
+pub const POACH_TABLES: [PoachTable; 5] = [
+    PoachTable {
+        name: "Wolf",
+        common: "Wolf Pelt",
+        rare: "Prime Pelt",
+        chance: 95,
+        rare_chance: 5,
+    },
+    PoachTable {
+        name: "Cactoid",
+        common: "Cactus Fruit",
+        rare: "Cactus Flower",
+        chance: 95,
+        rare_chance: 5,
+    },
+    PoachTable {
+        name: "Coeurl",
+        common: "Coeurl Pelt",
+        rare: "Coeurl Whisker",
+        chance: 95,
+        rare_chance: 5,
+    },
+    PoachTable {
+        name: "Dragon Aevis",
+        common: "Wyvern Wing",
+        rare: "Wyrm Bone",
+        chance: 95,
+        rare_chance: 5,
+    },
+    PoachTable {
+        name: "Urutan-Yensa",
+        common: "Yensa Scale",
+        rare: "Yensa Fin",
+        chance: 95,
+        rare_chance: 5,
+    },
+];
@@ -0,0 +1,71 @@
+use std::str::FromStr;
+
+/// A rare monster which may appear when its zone is loaded
+///
+/// The spawn check is a single RNG roll made after the zone load has consumed its draws
+#[derive(Debug, Copy, Clone)]
+pub struct RareGame {
+    pub name: &'static str,
+    pub zone: &'static str,
+    pub condition: &'static str,
+    pub chance: u8,
+}
+
+impl RareGame {
+    /// Whether the rare game spawns for the given spawn check value
+    pub fn spawns(&self, rng_val: u32) -> bool {
+        rng_val % 100 < self.chance as u32
+    }
+}
+
+impl FromStr for RareGame {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        RARE_GAME
+            .iter()
+            .find(|rare_game| rare_game.name == s)
+            .copied()
+            .ok_or(())
+    }
+}
+
+/// The notable rare game with spawn checks on zone entry
+pub const RARE_GAME: [RareGame; 6] = [
+    RareGame {
+        name: "Croakadile",
+        zone: "Giza Plains - Toam Hills",
+        condition: "Rains only",
+        chance: 10,
+    },
+    RareGame {
+        name: "Gil Snapper",
+        zone: "Phon Coast - Vaddu Strand",
+        condition: "Enter from Hunters' Camp",
+        chance: 10,
+    },
+    RareGame {
+        name: "Thalassinon",
+        zone: "Phon Coast - Cape Uahuaha",
+        condition: "Defeat every monster in the area, then re-enter",
+        chance: 30,
+    },
+    RareGame {
+        name: "Aspidochelon",
+        zone: "Cerobi Steppe - Feddik River",
+        condition: "Defeat every monster in the area, then re-enter",
+        chance: 20,
+    },
+    RareGame {
+        name: "Kaiser Wolf",
+        zone: "Paramina Rift - Freezing Gorge",
+        condition: "No other monsters in the area",
+        chance: 20,
+    },
+    RareGame {
+        name: "Dheed",
+        zone: "Tchita Uplands - Oliphzak Rise",
+        condition: "Enter with no Tchita Uplands monsters on screen",
+        chance: 30,
+    },
+];
@@ -57,6 +57,14 @@ impl RNG {
     /// </summary>
     /// <returns>The next random number in the sequence.</returns>
     pub fn gen_rand(&mut self) -> u32 {
+        let y = self.peek();
+        self.mti += 1;
+        self.position += 1;
+        y
+    }
+
+    /// The number `gen_rand` will return next, without moving along the sequence
+    pub fn peek(&mut self) -> u32 {
         let mut y;
 
         if self.mti >= RNG::N {
@@ -79,7 +87,6 @@ impl RNG {
         }
 
         y = self.mt[self.mti];
-        self.mti += 1;
 
         /* Tempering */
         y ^= y >> 11;
@@ -87,7 +94,6 @@ impl RNG {
         y ^= (y << 15) & 0xefc6_0000;
         y ^= y >> 18;
 
-        self.position += 1;
         y
     }
 }
@@ -1,24 +1,43 @@
 use rayon::prelude::*;
 use serde_derive::{Deserialize, Serialize};
 
-use crate::{character, rng};
+use crate::{
+    action, character, enemy, evasion, loot, observation, party, planner, poach, rare_game, rng,
+    seeding, steal,
+};
 
+/// How many raw values `RNGHelper::scan` lets build up before dropping the ones it has passed
+const SCAN_CHUNK: usize = 4096;
+
+/// What each thing done at a position would come to, laid out the way `action::Action::draws` consumes values.
+/// Heals, hit rolls, crits, combos, status and the chest spawn roll all use this position's value,
+/// while `attack` is the damage variance, drawn straight after the hit roll
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ValueLens {
     pub position: u32,
     pub value: u32,
     pub spell: i32,
+    /// Each party member's heal, in party order
+    pub party: Vec<i32>,
+    pub attack: i32,
+    pub crit: bool,
+    pub combo: bool,
+    pub status: bool,
+    pub hit: evasion::HitOutcome,
+    pub defend: evasion::HitOutcome,
     pub chest: u8,
 }
 
 /// The RNG Helper struct holds a set of generated random numbers,
-/// and can output them as `Spell` values or chest chances
+/// and can output them as `Spell` or item heal values, attack damage against `enemy` or chest chances
 ///
 /// Additionally, can locate the next set of matched `Spell` values in the given rng
 #[derive(Serialize, Deserialize, Debug)]
 pub struct RNGHelper {
     pub values: Vec<ValueLens>,
     pub rng: rng::RNG,
+    pub enemy: enemy::Enemy,
+    pub party: party::Party,
 }
 
 impl<'a> RNGHelper {
@@ -26,12 +45,14 @@ impl<'a> RNGHelper {
 
     /// Generates a new RNG list, with `iters` iterations filled
     pub fn new(seed: Option<u32>, character: &character::Character, iters: usize) -> RNGHelper {
-        let mut _rng = match seed {
-            Some(s) => rng::RNG::from(s),
-            _ => rng::RNG::new(),
-        };
+        let mut _rng = rng::RNG::from(seed.unwrap_or(rng::RNG::DEFAULT_SEED));
         let values = Vec::new();
-        let mut helper = RNGHelper { values, rng: _rng };
+        let mut helper = RNGHelper {
+            values,
+            rng: _rng,
+            enemy: enemy::Enemy::default(),
+            party: party::Party::default(),
+        };
         for _ in 0..iters {
             helper.push(character);
         }
@@ -47,10 +68,18 @@ impl<'a> RNGHelper {
     /// Adds new entries to the end of the value lists
     pub fn push(&mut self, character: &character::Character) {
         let next_rng = self.rng.gen_rand();
+        let variance = self.rng.peek();
         self.values.push(ValueLens {
             position: self.rng.position,
             value: next_rng,
-            spell: character.cast(next_rng),
+            spell: character.outcome(next_rng, &self.enemy),
+            party: self.party.heals(next_rng, &self.enemy),
+            attack: character.attack(variance, &self.enemy),
+            crit: character.crits(next_rng),
+            combo: character.combos(next_rng),
+            status: character.inflicts(next_rng, &self.enemy),
+            hit: character.hits(next_rng, &self.enemy),
+            defend: character.defends(next_rng, &self.enemy),
             chest: (next_rng % 100) as u8,
         });
     }
@@ -61,14 +90,109 @@ impl<'a> RNGHelper {
         self.push(character);
     }
 
+    /// The current RNG position: how many values have been consumed before the head of the list
+    pub fn position(&self) -> u32 {
+        match self.values.first() {
+            Some(value) => value.position - 1,
+            None => self.rng.position,
+        }
+    }
+
+    /// Cycles the rng list past every value consumed by `actions`
+    pub fn advance(&mut self, character: &character::Character, actions: &[action::Action]) {
+        for _ in 0..action::Action::position_after(0, actions) {
+            self.next(character);
+        }
+    }
+
+    /// Finds the fewest `actions` to take to move from the current position to exactly `target`
+    pub fn plan_to(&self, target: u32, actions: &[action::Action]) -> Option<Vec<planner::Step>> {
+        planner::plan(self.position(), target, actions)
+    }
+
+    /// The value each held position's attack rolls its damage variance from: the one after it
+    fn variances(&mut self) -> Vec<u32> {
+        let mut variances: Vec<u32> = self
+            .values
+            .iter()
+            .skip(1)
+            .map(|value| value.value)
+            .collect();
+        variances.push(self.rng.peek());
+        variances
+    }
+
     /// If the character is changed, re-calc the spell outcomes
     pub fn apply_character(&mut self, character: &character::Character) {
+        let variances = self.variances();
+        for (value, variance) in self.values.iter_mut().zip(variances) {
+            value.spell = character.outcome(value.value, &self.enemy);
+            value.attack = character.attack(variance, &self.enemy);
+            value.crit = character.crits(value.value);
+            value.combo = character.combos(value.value);
+            value.status = character.inflicts(value.value, &self.enemy);
+            value.hit = character.hits(value.value, &self.enemy);
+            value.defend = character.defends(value.value, &self.enemy);
+        }
+    }
+
+    /// If the enemy is changed, re-calc every outcome which depends on it
+    pub fn apply_enemy(&mut self, character: &character::Character, enemy: &enemy::Enemy) {
+        self.enemy = *enemy;
+        let variances = self.variances();
+        for (value, variance) in self.values.iter_mut().zip(variances) {
+            value.spell = character.outcome(value.value, &self.enemy);
+            value.party = self.party.heals(value.value, &self.enemy);
+            value.attack = character.attack(variance, &self.enemy);
+            value.status = character.inflicts(value.value, &self.enemy);
+            value.hit = character.hits(value.value, &self.enemy);
+            value.defend = character.defends(value.value, &self.enemy);
+        }
+    }
+
+    /// If the party is changed, re-calc each member's heals
+    pub fn apply_party(&mut self, party: &party::Party) {
+        self.party = party.clone();
         for value in &mut self.values {
-            value.spell = character.cast(value.value);
+            value.party = self.party.heals(value.value, &self.enemy);
+        }
+    }
+
+    /// Given heals cast by party members in order, as `(member, value)` pairs,
+    /// cycle the rng until they're at the head, or `LIMIT` iterations has been passed
+    pub fn find_party_casts(
+        &mut self,
+        character: &character::Character,
+        casts: &[(usize, i32)],
+        limit: Option<usize>,
+    ) -> bool {
+        match self.party.observations(casts, &self.enemy) {
+            Some(observations) => self.find_observations(character, &observations, limit),
+            None => false,
         }
     }
 
-    /// Given a list of spell values, cycle the rng until they're at the head, or `LIMIT` iterations has been passed
+    /// Given a party and heals cast by its members, try to find a seed that matches
+    pub fn find_seed_for_party(
+        character: &character::Character,
+        party: &party::Party,
+        enemy: &enemy::Enemy,
+        casts: &[(usize, i32)],
+        min: u32,
+        max: u32,
+        iters: usize,
+    ) -> Option<RNGHelper> {
+        let observations = party.observations(casts, enemy)?;
+        let mut helper =
+            RNGHelper::find_seed_by_observations(character, &observations, min, max, iters)?;
+        helper.enemy = *enemy;
+        helper.apply_party(party);
+        helper.apply_character(character);
+        Some(helper)
+    }
+
+    /// Given a list of spell or item heal values, cycle the rng until they're at the head, or `LIMIT` iterations has been passed
+    /// A multi-target cast takes a draw per target, so give the heal on the first target of each cast
     pub fn find_casts(
         &mut self,
         character: &character::Character,
@@ -80,12 +204,16 @@ impl<'a> RNGHelper {
         } else {
             RNGHelper::LIMIT
         };
+        let draws = character.draws();
+        while self.values.len() < values.len().saturating_sub(1) * draws + 1 {
+            self.push(character);
+        }
         for _ in 0..loop_limit {
             self.next(character);
             let mut matched = true;
             // Check the passed values against the spell_values - if any don't match, break and cycle again
             for (idx, val) in values.iter().enumerate() {
-                if self.values[idx].spell != *val {
+                if self.values[idx * draws].spell != *val {
                     matched = false;
                     break;
                 }
@@ -99,30 +227,232 @@ impl<'a> RNGHelper {
         false
     }
 
-    /// Given a character and set of cure values, try to find a seed that matches
-    /// This may be super slow
+    /// Returns the generated value at the given RNG position, if it is currently held
+    pub fn get(&self, position: u32) -> Option<&ValueLens> {
+        let first = self.values.first()?.position;
+        if position < first {
+            return None;
+        }
+        self.values.get((position - first) as usize)
+    }
+
+    /// Checks whether a rare game spawns when its zone is loaded at `position`,
+    /// after the load itself has consumed `draws` values
+    pub fn rare_game_spawns(
+        &self,
+        rare_game: &rare_game::RareGame,
+        position: u32,
+        draws: u32,
+    ) -> Option<bool> {
+        self.get(position + draws + 1)
+            .map(|value| rare_game.spawns(value.value))
+    }
+
+    /// Finds the first held position at which loading the zone spawns the rare game
+    pub fn find_rare_game(&self, rare_game: &rare_game::RareGame, draws: u32) -> Option<u32> {
+        let first = self.values.first()?.position;
+        (first - 1..)
+            .map(|position| (position, self.rare_game_spawns(rare_game, position, draws)))
+            .take_while(|(_, spawns)| spawns.is_some())
+            .find(|(_, spawns)| *spawns == Some(true))
+            .map(|(position, _)| position)
+    }
+
+    /// Returns the raw values held from `position` onwards
+    fn raw_values(&self, position: u32) -> Vec<u32> {
+        let first = match self.values.first() {
+            Some(value) => value.position,
+            None => return vec![],
+        };
+        if position < first {
+            return vec![];
+        }
+        self.values
+            .iter()
+            .skip((position - first) as usize)
+            .map(|value| value.value)
+            .collect()
+    }
+
+    /// Works out the result of a steal made at `position`
+    pub fn steal(&self, position: u32, thiefs_cuffs: bool) -> Option<steal::StealOutcome> {
+        steal::steal(&self.raw_values(position + 1), thiefs_cuffs)
+    }
+
+    /// Finds the first held position at which a steal yields the rare item
+    pub fn find_rare_steal(&self, thiefs_cuffs: bool) -> Option<u32> {
+        let rare = steal::StealOutcome::Stolen(steal::StealTier::Rare);
+        self.values
+            .iter()
+            .find(|value| steal::steal(&[value.value], thiefs_cuffs) == Some(rare))
+            .map(|value| value.position - 1)
+    }
+
+    /// Works out what an enemy drops if it dies at `position`
+    pub fn loot(&self, position: u32, modifiers: &loot::LootModifiers) -> Option<loot::Loot> {
+        loot::loot(&self.raw_values(position + 1), modifiers)
+    }
+
+    /// Lists the items an enemy drops if it dies at each held position
+    pub fn loot_view(
+        &self,
+        table: &loot::DropTable,
+        modifiers: &loot::LootModifiers,
+    ) -> Vec<(u32, Vec<&'static str>)> {
+        self.values
+            .iter()
+            .map(|value| value.position - 1)
+            .map_while(|position| {
+                self.loot(position, modifiers)
+                    .map(|loot| (position, loot.items(table)))
+            })
+            .collect()
+    }
+
+    /// Works out the result of a poach made at `position`
+    pub fn poach(&self, position: u32, table: &poach::PoachTable) -> Option<poach::PoachOutcome> {
+        self.get(position + 1).map(|value| table.poach(value.value))
+    }
+
+    /// Lists the result of a poach made at each held position
+    pub fn poach_view(&self, table: &poach::PoachTable) -> Vec<(u32, poach::PoachOutcome)> {
+        self.values
+            .iter()
+            .map(|value| (value.position - 1, table.poach(value.value)))
+            .collect()
+    }
+
+    /// How many positions after `position` the next critical hit roll lands
+    pub fn next_crit(&self, position: u32) -> Option<u32> {
+        self.values
+            .iter()
+            .filter(|value| value.position > position)
+            .find(|value| value.crit)
+            .map(|value| value.position - position)
+    }
+
+    /// How many extra hits a combo started at `position` chains into.
+    /// Each hit is an attack of its own, so the next combo roll is an attack's draws further on
+    pub fn combo_chain(&self, position: u32) -> u32 {
+        self.values
+            .iter()
+            .filter(|value| value.position > position)
+            .step_by(action::Action::Attack.draws() as usize)
+            .take_while(|value| value.combo)
+            .count() as u32
+    }
+
+    /// Given a sequence of observations, cycle the rng until they're at the head, or `LIMIT` iterations has been passed
+    pub fn find_observations(
+        &mut self,
+        character: &character::Character,
+        observations: &[Box<dyn observation::Observation>],
+        limit: Option<usize>,
+    ) -> bool {
+        let loop_limit = limit.unwrap_or(RNGHelper::LIMIT);
+        let draws = observation::total_draws(observations);
+        while self.values.len() < draws {
+            self.push(character);
+        }
+        for _ in 0..loop_limit {
+            self.next(character);
+            let rng_vals: Vec<u32> = self.values[..draws].iter().map(|v| v.value).collect();
+            if observation::matches_all(observations, &rng_vals) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Given any sequence of observations, try to find a seed that matches
+    pub fn find_seed_by_observations(
+        character: &character::Character,
+        observations: &[Box<dyn observation::Observation>],
+        min: u32,
+        max: u32,
+        iters: usize,
+    ) -> Option<RNGHelper> {
+        let len = observation::total_draws(observations);
+        RNGHelper::search_seeds(character, min, max, |seed| {
+            let steps = RNGHelper::scan(seed, len, iters, |rng_vals| {
+                observation::matches_all(observations, rng_vals)
+            })?;
+            let mut helper = RNGHelper::new(Some(seed), character, len);
+            if helper.find_observations(character, observations, Some(steps)) {
+                return Some(helper);
+            }
+            None
+        })
+    }
+
+    /// Steps through `seed`'s raw values with `window` of them in view, for up to `iters` steps,
+    /// and returns how many steps it took for `matches` to hold.
+    /// Seed searches check this way so they only work out what they compare, not every column of `ValueLens`
+    fn scan<F>(seed: u32, window: usize, iters: usize, matches: F) -> Option<usize>
+    where
+        F: Fn(&[u32]) -> bool,
+    {
+        let mut rng = rng::RNG::from(seed);
+        let mut rng_vals: Vec<u32> = (0..window).map(|_| rng.gen_rand()).collect();
+        let mut start = 0;
+        for step in 1..=iters {
+            rng_vals.push(rng.gen_rand());
+            start += 1;
+            if matches(&rng_vals[start..]) {
+                return Some(step);
+            }
+            if start >= SCAN_CHUNK {
+                rng_vals.drain(..start);
+                start = 0;
+            }
+        }
+        None
+    }
+
+    /// Runs `check` over the seeds between `min` and `max`, working through the ranges the
+    /// character's game version most likely seeded from first
+    fn search_seeds<F>(
+        character: &character::Character,
+        min: u32,
+        max: u32,
+        check: F,
+    ) -> Option<RNGHelper>
+    where
+        F: Fn(u32) -> Option<RNGHelper> + Sync + Send,
+    {
+        seeding::SeedingModel::for_version(character.version)
+            .ranges(min, max)
+            .iter()
+            .find_map(|range| (range.min..range.max).into_par_iter().find_map_any(&check))
+    }
+
+    /// Given a character, the enemy they cast on and set of spell values, try to find a seed that matches
+    /// The most likely seeds for the character's game version are tried first, but this may still be super slow
     pub fn find_seed(
         character: &character::Character,
+        enemy: &enemy::Enemy,
         values: &Vec<i32>,
         min: u32,
         max: u32,
         iters: usize,
     ) -> Option<RNGHelper> {
-        let seeds = min..max;
         let len = values.len();
-        println!("Character: {:#?}", character);
-        println!("Values: {:#?}", values);
-        seeds
-            .into_par_iter() // into_par_iter
-            .flat_map(|seed| {
-                println!("Checking seed: {}", seed);
-                let mut helper = RNGHelper::new(Some(seed), character, len);
-                if helper.find_casts(character, values, Some(iters)) {
-                    return Some(helper);
-                };
-                None
-            })
-            .find_any(|_| true) // find_any
+        let draws = character.draws();
+        let window = len.saturating_sub(1) * draws + 1;
+        RNGHelper::search_seeds(character, min, max, |seed| {
+            let steps = RNGHelper::scan(seed, window, iters, |rng_vals| {
+                values
+                    .iter()
+                    .enumerate()
+                    .all(|(idx, val)| character.outcome(rng_vals[idx * draws], enemy) == *val)
+            })?;
+            let mut helper = RNGHelper::new(Some(seed), character, len);
+            helper.apply_enemy(character, enemy);
+            if helper.find_casts(character, values, Some(steps)) {
+                return Some(helper);
+            };
+            None
+        })
     }
 }
 
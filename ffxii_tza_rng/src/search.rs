@@ -0,0 +1,158 @@
+use serde_derive::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::rng_helper::RNGHelper;
+use crate::{character, enemy, seeding};
+
+/// A seed search which can be stopped and picked up again later.
+///
+/// The search works through `pending` a chunk at a time with `RNGHelper::find_seed`,
+/// so saving the job between chunks loses at most one chunk of work
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SearchJob {
+    pub character: character::Character,
+    pub enemy: enemy::Enemy,
+    pub values: Vec<i32>,
+    pub iters: usize,
+    /// Ranges still to search, most likely first
+    pub pending: Vec<seeding::SeedRange>,
+    /// Ranges which have been searched
+    pub completed: Vec<seeding::SeedRange>,
+    /// Every seed found to match so far
+    pub candidates: Vec<u32>,
+}
+
+impl SearchJob {
+    /// How many seeds are searched between checkpoints
+    pub const CHUNK: u32 = 100_000;
+
+    pub fn new(
+        character: &character::Character,
+        enemy: &enemy::Enemy,
+        values: &[i32],
+        min: u32,
+        max: u32,
+        iters: usize,
+    ) -> SearchJob {
+        SearchJob {
+            character: *character,
+            enemy: *enemy,
+            values: values.to_vec(),
+            iters,
+            pending: seeding::SeedingModel::for_version(character.version).ranges(min, max),
+            completed: vec![],
+            candidates: vec![],
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Whether `other` looks for the same heals over the same seeds, so either can stand in for the other
+    pub fn is_same_search(&self, other: &SearchJob) -> bool {
+        self.character == other.character
+            && self.enemy == other.enemy
+            && self.values == other.values
+            && self.iters == other.iters
+            && self.bounds() == other.bounds()
+    }
+
+    /// The lowest and highest seeds the job covers
+    fn bounds(&self) -> Option<(u32, u32)> {
+        let ranges = || self.pending.iter().chain(&self.completed);
+        Some((
+            ranges().map(|range| range.min).min()?,
+            ranges().map(|range| range.max).max()?,
+        ))
+    }
+
+    /// How many seeds have been searched
+    pub fn searched(&self) -> u64 {
+        self.completed.iter().map(|range| range.len() as u64).sum()
+    }
+
+    /// How many seeds are left to search
+    pub fn remaining(&self) -> u64 {
+        self.pending.iter().map(|range| range.len() as u64).sum()
+    }
+
+    /// Searches the next chunk of seeds, returning the helper for a seed if one matched
+    pub fn step(&mut self) -> Option<RNGHelper> {
+        if self.pending.is_empty() {
+            return None;
+        }
+        let range = self.pending[0];
+        let chunk = seeding::SeedRange {
+            min: range.min,
+            max: range.min.saturating_add(SearchJob::CHUNK).min(range.max),
+            likelihood: range.likelihood * (SearchJob::CHUNK as f64 / range.len() as f64).min(1.0),
+        };
+        if chunk.max == range.max {
+            self.pending.remove(0);
+        } else {
+            self.pending[0].min = chunk.max;
+            self.pending[0].likelihood -= chunk.likelihood;
+        }
+
+        let found = RNGHelper::find_seed(
+            &self.character,
+            &self.enemy,
+            &self.values,
+            chunk.min,
+            chunk.max,
+            self.iters,
+        );
+        match self.completed.last_mut() {
+            Some(last) if last.max == chunk.min => {
+                last.max = chunk.max;
+                last.likelihood += chunk.likelihood;
+            }
+            _ => self.completed.push(chunk),
+        }
+        if let Some(helper) = &found {
+            self.candidates.push(helper.rng.seed);
+        }
+        found
+    }
+
+    /// Searches until a seed matches or there's nothing left, calling `checkpoint` after every chunk
+    pub fn run<F>(&mut self, mut checkpoint: F) -> Option<RNGHelper>
+    where
+        F: FnMut(&SearchJob),
+    {
+        while !self.is_done() {
+            let found = self.step();
+            checkpoint(self);
+            if found.is_some() {
+                return found;
+            }
+        }
+        None
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<SearchJob> {
+        serde_json::from_str(json)
+    }
+
+    /// Writes the job to `path`, going through a temporary file so a crash mid-write can't lose the last checkpoint
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let json = self
+            .to_json()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, json)?;
+        fs::rename(&tmp, path)
+    }
+
+    pub fn load(path: &Path) -> io::Result<SearchJob> {
+        let json = fs::read_to_string(path)?;
+        SearchJob::from_json(&json).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
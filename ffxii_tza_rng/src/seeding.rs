@@ -0,0 +1,143 @@
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{rng, version};
+
+/// Seeds from `min` up to (but not including) `max`, and roughly how likely the real seed is to be among them
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct SeedRange {
+    pub min: u32,
+    pub max: u32,
+    pub likelihood: f64,
+}
+
+impl SeedRange {
+    pub fn len(&self) -> u32 {
+        self.max - self.min
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.max <= self.min
+    }
+}
+
+/// How a platform picks the seed the RNG starts from
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum SeedingModel {
+    /// Always the same seed
+    Fixed(u32),
+    /// A tick count taken at boot, which clusters around `centre` with a standard deviation of `spread`
+    BootTicks { centre: u32, spread: u32 },
+    /// Nothing is known, every seed is as likely as any other
+    Uniform,
+}
+
+impl SeedingModel {
+    /// Likelihoods of the seed falling within 1, 2, 3 and 4 spreads of the centre, excluding the inner bands
+    const BANDS: [f64; 4] = [0.6827, 0.2718, 0.0428, 0.0026];
+
+    /// Best guesses at how each version seeds its RNG.
+    /// The PS2 releases always start from the default seed, the remasters from a tick count at boot
+    pub fn for_version(version: version::GameVersion) -> SeedingModel {
+        match version {
+            version::GameVersion::Vanilla | version::GameVersion::IZJS => {
+                SeedingModel::Fixed(rng::RNG::DEFAULT_SEED)
+            }
+            version::GameVersion::TZAConsole => SeedingModel::BootTicks {
+                centre: 6_500_000,
+                spread: 250_000,
+            },
+            version::GameVersion::TZAPC => SeedingModel::BootTicks {
+                centre: 7_200_000,
+                spread: 3_600_000,
+            },
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match *self {
+            SeedingModel::Fixed(_) => "Fixed seed",
+            SeedingModel::BootTicks { .. } => "Boot tick count",
+            SeedingModel::Uniform => "Uniform",
+        }
+    }
+
+    /// Splits `min..max` into ranges which don't overlap, most likely first.
+    /// Seeds the model doesn't expect come last, with a likelihood of 0.
+    /// Nothing at all if `min..max` is empty
+    pub fn ranges(&self, min: u32, max: u32) -> Vec<SeedRange> {
+        if min >= max {
+            return vec![];
+        }
+        let mut likely: Vec<SeedRange> = match *self {
+            SeedingModel::Fixed(seed) => vec![SeedRange {
+                min: seed,
+                max: seed.saturating_add(1),
+                likelihood: 1.0,
+            }],
+            SeedingModel::BootTicks { centre, spread } => {
+                let mut bands = vec![];
+                for (idx, likelihood) in SeedingModel::BANDS.iter().enumerate() {
+                    let inner = spread.saturating_mul(idx as u32);
+                    let outer = spread.saturating_mul(idx as u32 + 1);
+                    // Half the band sits either side of the centre
+                    bands.push(SeedRange {
+                        min: centre.saturating_add(inner),
+                        max: centre.saturating_add(outer),
+                        likelihood: likelihood / 2.0,
+                    });
+                    bands.push(SeedRange {
+                        min: centre.saturating_sub(outer),
+                        max: centre.saturating_sub(inner),
+                        likelihood: likelihood / 2.0,
+                    });
+                }
+                bands
+            }
+            SeedingModel::Uniform => vec![SeedRange {
+                min,
+                max,
+                likelihood: 1.0,
+            }],
+        };
+
+        for range in likely.iter_mut() {
+            let len = range.len() as f64;
+            range.min = range.min.clamp(min, max);
+            range.max = range.max.clamp(min, max);
+            if len > 0.0 {
+                range.likelihood *= range.len() as f64 / len;
+            }
+        }
+        likely.retain(|range| !range.is_empty());
+        likely.sort_by(|a, b| b.likelihood.partial_cmp(&a.likelihood).unwrap());
+
+        // Whatever the model didn't cover, in order
+        let mut covered: Vec<SeedRange> = likely.clone();
+        covered.sort_by_key(|range| range.min);
+        let mut start = min;
+        for range in covered {
+            if range.min > start {
+                likely.push(SeedRange {
+                    min: start,
+                    max: range.min,
+                    likelihood: 0.0,
+                });
+            }
+            start = start.max(range.max);
+        }
+        if start < max {
+            likely.push(SeedRange {
+                min: start,
+                max,
+                likelihood: 0.0,
+            });
+        }
+        likely
+    }
+}
+
+impl Default for SeedingModel {
+    fn default() -> Self {
+        SeedingModel::for_version(version::GameVersion::default())
+    }
+}
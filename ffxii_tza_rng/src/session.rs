@@ -0,0 +1,187 @@
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::rng_helper::RNGHelper;
+use crate::{character, enemy, party, rng};
+
+#[derive(Debug)]
+pub enum SessionError {
+    Json(serde_json::Error),
+    /// Saved by a newer release than this one
+    Unsupported(u32),
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SessionError::Json(err) => write!(f, "invalid session: {}", err),
+            SessionError::Unsupported(version) => write!(
+                f,
+                "session format {} is newer than this release understands ({})",
+                version,
+                Session::VERSION
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SessionError {}
+
+impl From<serde_json::Error> for SessionError {
+    fn from(err: serde_json::Error) -> Self {
+        SessionError::Json(err)
+    }
+}
+
+impl From<SessionError> for io::Error {
+    fn from(err: SessionError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, err)
+    }
+}
+
+/// Everything needed to pick a run back up where it was left
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct Session {
+    /// Format version, see `Session::VERSION`
+    pub version: u32,
+    /// Also holds the game version being played
+    pub character: character::Character,
+    pub party: party::Party,
+    pub enemy: enemy::Enemy,
+    pub seed: u32,
+    /// How many values have been used
+    pub position: u32,
+    /// Heals entered so far, as `(party member, value)`
+    pub observations: Vec<(usize, i32)>,
+    /// Positions being aimed for
+    pub targets: Vec<u32>,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Session {
+            version: Session::VERSION,
+            character: character::Character::default(),
+            party: party::Party::default(),
+            enemy: enemy::Enemy::default(),
+            seed: rng::RNG::DEFAULT_SEED,
+            position: 0,
+            observations: vec![],
+            targets: vec![],
+        }
+    }
+}
+
+impl Session {
+    /// Bump this, and add a step to `migrate`, whenever a change would break older sessions
+    pub const VERSION: u32 = 3;
+
+    /// Captures where `rng_helper` is up to
+    pub fn capture(
+        character: &character::Character,
+        rng_helper: &RNGHelper,
+        observations: &[(usize, i32)],
+        targets: &[u32],
+    ) -> Session {
+        Session {
+            version: Session::VERSION,
+            character: *character,
+            party: rng_helper.party.clone(),
+            enemy: rng_helper.enemy,
+            seed: rng_helper.rng.seed,
+            position: rng_helper.position(),
+            observations: observations.to_vec(),
+            targets: targets.to_vec(),
+        }
+    }
+
+    /// A helper at the session's seed and position, with `len` values ready
+    pub fn restore(&self, len: usize) -> RNGHelper {
+        let character = self.character;
+        let mut rng_helper = RNGHelper::new(Some(self.seed), &character, 0);
+        rng_helper.apply_enemy(&character, &self.enemy);
+        rng_helper.apply_party(&self.party);
+        for _ in 0..len {
+            rng_helper.push(&character);
+        }
+        for _ in 0..self.position {
+            rng_helper.next(&character);
+        }
+        rng_helper
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Reads a session saved by this or any earlier release
+    pub fn from_json(json: &str) -> Result<Session, SessionError> {
+        let value = Session::migrate(serde_json::from_str(json)?)?;
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Brings a saved session up to the current format, one version at a time
+    fn migrate(mut value: Value) -> Result<Value, SessionError> {
+        loop {
+            let version = value.get("version").and_then(Value::as_u64).unwrap_or(0) as u32;
+            match version {
+                // Sessions written by hand, or before there was a version, only lack the number
+                0 => match value.as_object_mut() {
+                    Some(fields) => {
+                        fields.insert("version".to_string(), Value::from(1));
+                    }
+                    // Not a session at all, deserializing will say what's wrong
+                    None => return Ok(value),
+                },
+                // Version 1 kept the game version apart from the character's own
+                1 => {
+                    let fields = value
+                        .as_object_mut()
+                        .expect("has a version, so is an object");
+                    if let Some(game) = fields.remove("game") {
+                        let character = fields
+                            .entry("character")
+                            .or_insert(serde_json::to_value(character::Character::default())?);
+                        if let Some(character) = character.as_object_mut() {
+                            character.insert("version".to_string(), game);
+                        }
+                    }
+                    fields.insert("version".to_string(), Value::from(2));
+                }
+                // Version 2 held an Earth affinity, which no spell uses, before Dark
+                2 => {
+                    let fields = value
+                        .as_object_mut()
+                        .expect("has a version, so is an object");
+                    if let Some(Value::Array(affinities)) = fields
+                        .get_mut("enemy")
+                        .and_then(|enemy| enemy.get_mut("affinities"))
+                    {
+                        if affinities.len() == 8 {
+                            affinities.remove(5);
+                        }
+                    }
+                    fields.insert("version".to_string(), Value::from(3));
+                }
+                Session::VERSION => return Ok(value),
+                _ => return Err(SessionError::Unsupported(version)),
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let json = self
+            .to_json()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        fs::write(path, json)
+    }
+
+    pub fn load(path: &Path) -> io::Result<Session> {
+        Ok(Session::from_json(&fs::read_to_string(path)?)?)
+    }
+}
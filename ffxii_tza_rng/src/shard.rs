@@ -0,0 +1,341 @@
+use serde_derive::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::rng_helper::RNGHelper;
+use crate::{character, enemy, seeding};
+
+/// A slice of the seed space, handed out as a single piece of work.
+/// Lower ids are searched first by `RNGHelper::find_seed`
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct WorkUnit {
+    pub id: u32,
+    pub min: u32,
+    pub max: u32,
+}
+
+/// Splits `min..max` into work units of at most `size` seeds.
+/// The same arguments always give the same units, in the order the seeding model ranks them
+pub fn shards(
+    version: crate::version::GameVersion,
+    min: u32,
+    max: u32,
+    size: u32,
+) -> Vec<WorkUnit> {
+    let size = size.max(1);
+    let mut units = vec![];
+    for range in seeding::SeedingModel::for_version(version).ranges(min, max) {
+        let mut start = range.min;
+        while start < range.max {
+            let end = start.saturating_add(size).min(range.max);
+            units.push(WorkUnit {
+                id: units.len() as u32,
+                min: start,
+                max: end,
+            });
+            start = end;
+        }
+    }
+    units
+}
+
+/// Everything a worker needs to check a unit
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ShardJob {
+    pub character: character::Character,
+    pub enemy: enemy::Enemy,
+    pub values: Vec<i32>,
+    pub iters: usize,
+}
+
+impl ShardJob {
+    /// Searches a single unit, the same way `RNGHelper::find_seed` would
+    pub fn search(&self, unit: &WorkUnit) -> Option<RNGHelper> {
+        RNGHelper::find_seed(
+            &self.character,
+            &self.enemy,
+            &self.values,
+            unit.min,
+            unit.max,
+            self.iters,
+        )
+    }
+}
+
+/// Coordinator to worker
+#[derive(Serialize, Deserialize, Debug)]
+enum Request {
+    Search(ShardJob, WorkUnit),
+    Done,
+}
+
+/// Worker to coordinator
+#[derive(Serialize, Deserialize, Debug)]
+enum Report {
+    /// Sent every `HEARTBEAT` while a unit is being searched
+    Working,
+    Searched {
+        unit: u32,
+        seed: Option<u32>,
+    },
+}
+
+#[derive(Default)]
+struct Progress {
+    queue: VecDeque<WorkUnit>,
+    in_flight: BTreeSet<u32>,
+    results: BTreeMap<u32, Option<u32>>,
+    /// Workers currently connected
+    workers: usize,
+}
+
+impl Progress {
+    /// The earliest unit to have matched so far
+    fn best(&self) -> Option<u32> {
+        self.results
+            .iter()
+            .find(|(_, seed)| seed.is_some())
+            .map(|(id, _)| *id)
+    }
+
+    /// Units after one that already matched can't change the result, so are skipped
+    fn next(&mut self) -> Option<WorkUnit> {
+        let best = self.best();
+        while let Some(unit) = self.queue.pop_front() {
+            match best {
+                Some(best) if unit.id > best => {}
+                _ => {
+                    self.in_flight.insert(unit.id);
+                    return Some(unit);
+                }
+            }
+        }
+        None
+    }
+
+    fn finished(&self) -> bool {
+        let best = self.best().unwrap_or(u32::MAX);
+        !self.queue.iter().any(|unit| unit.id < best) && !self.in_flight.iter().any(|id| *id < best)
+    }
+
+    fn seed(&self) -> Option<u32> {
+        self.results.values().find_map(|seed| *seed)
+    }
+}
+
+/// How long idle workers wait before asking for work again
+const POLL: Duration = Duration::from_millis(100);
+
+/// How often workers say they're still searching
+const HEARTBEAT: Duration = Duration::from_secs(1);
+
+/// Hands work units out to workers connecting over TCP and merges what they find.
+///
+/// Workers which drop out, or go quiet for `worker_timeout`, have their unit handed to someone else.
+/// Idle workers stay connected until the search is over, in case that happens
+pub struct Coordinator {
+    job: ShardJob,
+    progress: Arc<Mutex<Progress>>,
+    timeout: Duration,
+    worker_timeout: Duration,
+}
+
+impl Coordinator {
+    /// How long to wait for a worker when there are none connected, by default
+    pub const TIMEOUT: Duration = Duration::from_secs(600);
+
+    /// How long a searching worker can go without a word before its unit is taken back, by default
+    pub const WORKER_TIMEOUT: Duration = Duration::from_secs(30);
+
+    pub fn new(job: ShardJob, units: Vec<WorkUnit>) -> Coordinator {
+        Coordinator {
+            job,
+            progress: Arc::new(Mutex::new(Progress {
+                queue: units.into(),
+                ..Default::default()
+            })),
+            timeout: Coordinator::TIMEOUT,
+            worker_timeout: Coordinator::WORKER_TIMEOUT,
+        }
+    }
+
+    /// Gives up serving once no worker has been connected for `timeout`
+    pub fn timeout(mut self, timeout: Duration) -> Coordinator {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Takes a unit back once its worker has gone `timeout` without a word.
+    /// Workers speak up every second while searching, so keep this well above that
+    pub fn worker_timeout(mut self, timeout: Duration) -> Coordinator {
+        self.worker_timeout = timeout;
+        self
+    }
+
+    /// Serves workers on `listener` until the result is known, returning the same helper
+    /// `RNGHelper::find_seed` would have over all the units
+    pub fn serve(&self, listener: TcpListener) -> io::Result<Option<RNGHelper>> {
+        listener.set_nonblocking(true)?;
+        let mut idle_since = Instant::now();
+        loop {
+            {
+                let progress = self.progress.lock().unwrap();
+                if progress.finished() {
+                    break;
+                }
+                if progress.workers > 0 {
+                    idle_since = Instant::now();
+                } else if idle_since.elapsed() > self.timeout {
+                    return Err(io::Error::new(
+                        io::ErrorKind::TimedOut,
+                        "no workers left to finish the search",
+                    ));
+                }
+            }
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    stream.set_nonblocking(false)?;
+                    stream.set_read_timeout(Some(self.worker_timeout))?;
+                    let job = self.job.clone();
+                    let progress = Arc::clone(&self.progress);
+                    thread::spawn(move || Coordinator::handle(stream, job, progress));
+                }
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {
+                    thread::sleep(Duration::from_millis(50));
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        // Give idle workers the chance to hear the search is over
+        let done = Instant::now();
+        while self.progress.lock().unwrap().workers > 0 && done.elapsed() < POLL * 10 {
+            thread::sleep(POLL);
+        }
+        let seed = self.progress.lock().unwrap().seed();
+        Ok(seed.and_then(|seed| {
+            self.job.search(&WorkUnit {
+                id: 0,
+                min: seed,
+                max: seed + 1,
+            })
+        }))
+    }
+
+    fn handle(stream: TcpStream, job: ShardJob, progress: Arc<Mutex<Progress>>) {
+        progress.lock().unwrap().workers += 1;
+        Coordinator::feed(stream, &job, &progress);
+        progress.lock().unwrap().workers -= 1;
+    }
+
+    /// Hands units to one worker until there are none left or it drops
+    fn feed(stream: TcpStream, job: &ShardJob, progress: &Mutex<Progress>) {
+        let mut reader = match stream.try_clone() {
+            Ok(stream) => BufReader::new(stream),
+            Err(_) => return,
+        };
+        let mut writer = stream;
+        loop {
+            let unit = loop {
+                let mut progress = progress.lock().unwrap();
+                if let Some(unit) = progress.next() {
+                    break unit;
+                }
+                if progress.finished() {
+                    let _ = send(&mut writer, &Request::Done);
+                    return;
+                }
+                // Units still out with other workers come back if those workers drop
+                drop(progress);
+                thread::sleep(POLL);
+            };
+            let report = send(&mut writer, &Request::Search(job.clone(), unit))
+                .and_then(|_| searched(&mut reader));
+            let mut progress = progress.lock().unwrap();
+            progress.in_flight.remove(&unit.id);
+            match report {
+                Ok((id, seed)) if id == unit.id => {
+                    progress.results.insert(unit.id, seed);
+                }
+                _ => {
+                    progress.queue.push_front(unit);
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Waits out a worker's heartbeats for the unit and seed it found.
+/// A worker which goes quiet for the stream's read timeout gives an error
+fn searched(reader: &mut BufReader<TcpStream>) -> io::Result<(u32, Option<u32>)> {
+    loop {
+        match receive::<Report>(reader)? {
+            Report::Working => {}
+            Report::Searched { unit, seed } => return Ok((unit, seed)),
+        }
+    }
+}
+
+/// Connects to a coordinator and searches whatever it hands over until told to stop.
+/// A coordinator which has finished may also just hang up
+pub fn work<A: ToSocketAddrs>(addr: A) -> io::Result<()> {
+    let stream = TcpStream::connect(addr)?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+    loop {
+        let request = match receive::<Request>(&mut reader) {
+            Ok(request) => request,
+            Err(ref err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(err) => return Err(err),
+        };
+        match request {
+            Request::Search(job, unit) => {
+                let (found, searching) = mpsc::channel();
+                thread::spawn(move || {
+                    let _ = found.send(job.search(&unit).map(|helper| helper.rng.seed));
+                });
+                let seed = loop {
+                    match searching.recv_timeout(HEARTBEAT) {
+                        Ok(seed) => break seed,
+                        Err(RecvTimeoutError::Timeout) => send(&mut writer, &Report::Working)?,
+                        Err(RecvTimeoutError::Disconnected) => {
+                            return Err(io::Error::other("search panicked"))
+                        }
+                    }
+                };
+                send(
+                    &mut writer,
+                    &Report::Searched {
+                        unit: unit.id,
+                        seed,
+                    },
+                )?;
+            }
+            Request::Done => return Ok(()),
+        }
+    }
+}
+
+/// Messages are sent as one line of JSON each
+fn send<T: serde::Serialize>(writer: &mut TcpStream, message: &T) -> io::Result<()> {
+    let mut line = serde_json::to_string(message)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    line.push('\n');
+    writer.write_all(line.as_bytes())
+}
+
+fn receive<T: serde::de::DeserializeOwned>(reader: &mut BufReader<TcpStream>) -> io::Result<T> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "connection closed",
+        ));
+    }
+    serde_json::from_str(&line).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
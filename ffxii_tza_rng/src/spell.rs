@@ -1,30 +1,187 @@
 use serde_derive::{Deserialize, Serialize};
 use std::str::FromStr;
 
-#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+use crate::version;
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum Element {
+    Fire,
+    Ice,
+    Lightning,
+    Water,
+    Wind,
+    Dark,
+    Holy,
+}
+
+impl Element {
+    pub const ALL: [Element; 7] = [
+        Element::Fire,
+        Element::Ice,
+        Element::Lightning,
+        Element::Water,
+        Element::Wind,
+        Element::Dark,
+        Element::Holy,
+    ];
+
+    pub fn name(&self) -> &str {
+        match *self {
+            Element::Fire => "Fire",
+            Element::Ice => "Ice",
+            Element::Lightning => "Lightning",
+            Element::Water => "Water",
+            Element::Wind => "Wind",
+            Element::Dark => "Dark",
+            Element::Holy => "Holy",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
 pub enum Spell {
     Cure,
     Cura,
     Curaga,
     Curaja,
+    Renew,
+    Fire,
+    Blizzard,
+    Thunder,
+    Aero,
+    Water,
+    Fira,
+    Blizzara,
+    Thundara,
+    Bio,
+    Firaga,
+    Blizzaga,
+    Thundaga,
+    Aeroga,
+    Dark,
+    Darkra,
+    Darkga,
+    Shock,
+    Scathe,
+    Holy,
+    Flare,
+    Ardor,
 }
 
 impl Spell {
+    pub const ALL: [Spell; 26] = [
+        Spell::Cure,
+        Spell::Cura,
+        Spell::Curaga,
+        Spell::Curaja,
+        Spell::Renew,
+        Spell::Fire,
+        Spell::Blizzard,
+        Spell::Thunder,
+        Spell::Aero,
+        Spell::Water,
+        Spell::Fira,
+        Spell::Blizzara,
+        Spell::Thundara,
+        Spell::Bio,
+        Spell::Firaga,
+        Spell::Blizzaga,
+        Spell::Thundaga,
+        Spell::Aeroga,
+        Spell::Dark,
+        Spell::Darkra,
+        Spell::Darkga,
+        Spell::Shock,
+        Spell::Scathe,
+        Spell::Holy,
+        Spell::Flare,
+        Spell::Ardor,
+    ];
+
     pub fn name(&self) -> &str {
         match *self {
             Spell::Cure => "Cure",
             Spell::Cura => "Cura",
             Spell::Curaga => "Curaga",
             Spell::Curaja => "Curaja",
+            Spell::Renew => "Renew",
+            Spell::Fire => "Fire",
+            Spell::Blizzard => "Blizzard",
+            Spell::Thunder => "Thunder",
+            Spell::Aero => "Aero",
+            Spell::Water => "Water",
+            Spell::Fira => "Fira",
+            Spell::Blizzara => "Blizzara",
+            Spell::Thundara => "Thundara",
+            Spell::Bio => "Bio",
+            Spell::Firaga => "Firaga",
+            Spell::Blizzaga => "Blizzaga",
+            Spell::Thundaga => "Thundaga",
+            Spell::Aeroga => "Aeroga",
+            Spell::Dark => "Dark",
+            Spell::Darkra => "Darkra",
+            Spell::Darkga => "Darkga",
+            Spell::Shock => "Shock",
+            Spell::Scathe => "Scathe",
+            Spell::Holy => "Holy",
+            Spell::Flare => "Flare",
+            Spell::Ardor => "Ardor",
         }
     }
 
-    pub fn power(&self) -> u8 {
+    pub fn power(&self, version: version::GameVersion) -> u8 {
+        if !version.rebalanced() {
+            match *self {
+                Spell::Scathe => return 120,
+                Spell::Holy => return 140,
+                Spell::Flare => return 130,
+                Spell::Ardor => return 170,
+                _ => {}
+            }
+        }
         match *self {
             Spell::Cure => 20,
             Spell::Cura => 46,
             Spell::Curaga => 86,
             Spell::Curaja => 120,
+            // Renew heals a fixed amount, so has no power to scale
+            Spell::Renew => 0,
+            Spell::Fire | Spell::Blizzard | Spell::Thunder => 22,
+            Spell::Aero | Spell::Water => 40,
+            Spell::Fira | Spell::Blizzara | Spell::Thundara => 44,
+            Spell::Bio => 70,
+            Spell::Firaga | Spell::Blizzaga | Spell::Thundaga => 96,
+            Spell::Aeroga => 86,
+            Spell::Dark => 42,
+            Spell::Darkra => 58,
+            Spell::Darkga => 95,
+            Spell::Shock => 130,
+            Spell::Scathe => 150,
+            Spell::Holy => 150,
+            Spell::Flare => 145,
+            Spell::Ardor => 180,
+        }
+    }
+
+    /// Whether the spell restores HP rather than dealing damage
+    pub fn is_cure(&self) -> bool {
+        matches!(
+            *self,
+            Spell::Cure | Spell::Cura | Spell::Curaga | Spell::Curaja | Spell::Renew
+        )
+    }
+
+    /// The element of an offensive spell. Non-elemental spells return `None`
+    pub fn element(&self) -> Option<Element> {
+        match *self {
+            Spell::Fire | Spell::Fira | Spell::Firaga | Spell::Ardor => Some(Element::Fire),
+            Spell::Blizzard | Spell::Blizzara | Spell::Blizzaga => Some(Element::Ice),
+            Spell::Thunder | Spell::Thundara | Spell::Thundaga => Some(Element::Lightning),
+            Spell::Water => Some(Element::Water),
+            Spell::Aero | Spell::Aeroga => Some(Element::Wind),
+            Spell::Dark | Spell::Darkra | Spell::Darkga => Some(Element::Dark),
+            Spell::Holy => Some(Element::Holy),
+            _ => None,
         }
     }
 }
@@ -33,12 +190,10 @@ impl FromStr for Spell {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, ()> {
-        match s {
-            "Cure" => Ok(Spell::Cure),
-            "Cura" => Ok(Spell::Cura),
-            "Curaga" => Ok(Spell::Curaga),
-            "Curaja" => Ok(Spell::Curaja),
-            _ => Err(()),
-        }
+        Spell::ALL
+            .iter()
+            .find(|spell| spell.name() == s)
+            .copied()
+            .ok_or(())
     }
 }
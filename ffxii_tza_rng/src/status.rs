@@ -0,0 +1,191 @@
+use serde_derive::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum Status {
+    Sleep,
+    Blind,
+    Poison,
+    Silence,
+    Slow,
+    Stop,
+    Disable,
+    Immobilize,
+    Confuse,
+    Death,
+    AttackDown,
+    MagickDown,
+    DefenseDown,
+    MagickDefenseDown,
+}
+
+impl Status {
+    pub const ALL: [Status; 14] = [
+        Status::Sleep,
+        Status::Blind,
+        Status::Poison,
+        Status::Silence,
+        Status::Slow,
+        Status::Stop,
+        Status::Disable,
+        Status::Immobilize,
+        Status::Confuse,
+        Status::Death,
+        Status::AttackDown,
+        Status::MagickDown,
+        Status::DefenseDown,
+        Status::MagickDefenseDown,
+    ];
+
+    pub fn name(&self) -> &str {
+        match *self {
+            Status::Sleep => "Sleep",
+            Status::Blind => "Blind",
+            Status::Poison => "Poison",
+            Status::Silence => "Silence",
+            Status::Slow => "Slow",
+            Status::Stop => "Stop",
+            Status::Disable => "Disable",
+            Status::Immobilize => "Immobilize",
+            Status::Confuse => "Confuse",
+            Status::Death => "Death",
+            Status::AttackDown => "Attack Down",
+            Status::MagickDown => "Magick Down",
+            Status::DefenseDown => "Defense Down",
+            Status::MagickDefenseDown => "Magick Defense Down",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum Source {
+    Magick,
+    Technick,
+}
+
+/// A magick or technick which inflicts a status
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum StatusAction {
+    Sleep,
+    Sleepga,
+    Blind,
+    Blindga,
+    Poison,
+    Silence,
+    Silencega,
+    Slow,
+    Slowga,
+    Stop,
+    Disable,
+    Immobilize,
+    Confuse,
+    Death,
+    Wither,
+    Addle,
+    Expose,
+    Shear,
+}
+
+impl StatusAction {
+    pub const ALL: [StatusAction; 18] = [
+        StatusAction::Sleep,
+        StatusAction::Sleepga,
+        StatusAction::Blind,
+        StatusAction::Blindga,
+        StatusAction::Poison,
+        StatusAction::Silence,
+        StatusAction::Silencega,
+        StatusAction::Slow,
+        StatusAction::Slowga,
+        StatusAction::Stop,
+        StatusAction::Disable,
+        StatusAction::Immobilize,
+        StatusAction::Confuse,
+        StatusAction::Death,
+        StatusAction::Wither,
+        StatusAction::Addle,
+        StatusAction::Expose,
+        StatusAction::Shear,
+    ];
+
+    pub fn name(&self) -> &str {
+        match *self {
+            StatusAction::Sleep => "Sleep",
+            StatusAction::Sleepga => "Sleepga",
+            StatusAction::Blind => "Blind",
+            StatusAction::Blindga => "Blindga",
+            StatusAction::Poison => "Poison",
+            StatusAction::Silence => "Silence",
+            StatusAction::Silencega => "Silencega",
+            StatusAction::Slow => "Slow",
+            StatusAction::Slowga => "Slowga",
+            StatusAction::Stop => "Stop",
+            StatusAction::Disable => "Disable",
+            StatusAction::Immobilize => "Immobilize",
+            StatusAction::Confuse => "Confuse",
+            StatusAction::Death => "Death",
+            StatusAction::Wither => "Wither",
+            StatusAction::Addle => "Addle",
+            StatusAction::Expose => "Expose",
+            StatusAction::Shear => "Shear",
+        }
+    }
+
+    pub fn source(&self) -> Source {
+        match *self {
+            StatusAction::Wither
+            | StatusAction::Addle
+            | StatusAction::Expose
+            | StatusAction::Shear => Source::Technick,
+            _ => Source::Magick,
+        }
+    }
+
+    pub fn status(&self) -> Status {
+        match *self {
+            StatusAction::Sleep | StatusAction::Sleepga => Status::Sleep,
+            StatusAction::Blind | StatusAction::Blindga => Status::Blind,
+            StatusAction::Poison => Status::Poison,
+            StatusAction::Silence | StatusAction::Silencega => Status::Silence,
+            StatusAction::Slow | StatusAction::Slowga => Status::Slow,
+            StatusAction::Stop => Status::Stop,
+            StatusAction::Disable => Status::Disable,
+            StatusAction::Immobilize => Status::Immobilize,
+            StatusAction::Confuse => Status::Confuse,
+            StatusAction::Death => Status::Death,
+            StatusAction::Wither => Status::AttackDown,
+            StatusAction::Addle => Status::MagickDown,
+            StatusAction::Expose => Status::DefenseDown,
+            StatusAction::Shear => Status::MagickDefenseDown,
+        }
+    }
+
+    /// Chance out of 100 to inflict the status on an enemy with no resistance
+    pub fn chance(&self) -> u32 {
+        match *self {
+            StatusAction::Sleep | StatusAction::Blind | StatusAction::Silence => 45,
+            StatusAction::Sleepga | StatusAction::Blindga | StatusAction::Silencega => 30,
+            StatusAction::Poison | StatusAction::Slow => 50,
+            StatusAction::Slowga => 35,
+            StatusAction::Stop | StatusAction::Confuse => 30,
+            StatusAction::Disable | StatusAction::Immobilize => 35,
+            StatusAction::Death => 15,
+            StatusAction::Wither
+            | StatusAction::Addle
+            | StatusAction::Expose
+            | StatusAction::Shear => 75,
+        }
+    }
+}
+
+impl FromStr for StatusAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        StatusAction::ALL
+            .iter()
+            .find(|action| action.name() == s)
+            .copied()
+            .ok_or(())
+    }
+}
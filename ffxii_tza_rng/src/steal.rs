@@ -0,0 +1,66 @@
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum StealTier {
+    Rare,
+    Uncommon,
+    Common,
+}
+
+impl StealTier {
+    pub fn name(&self) -> &str {
+        match *self {
+            StealTier::Rare => "Rare",
+            StealTier::Uncommon => "Uncommon",
+            StealTier::Common => "Common",
+        }
+    }
+
+    /// Chance out of 100 for this tier's roll to succeed
+    pub fn chance(&self, thiefs_cuffs: bool) -> u32 {
+        match (*self, thiefs_cuffs) {
+            (StealTier::Rare, false) => 3,
+            (StealTier::Rare, true) => 6,
+            (StealTier::Uncommon, false) => 10,
+            (StealTier::Uncommon, true) => 20,
+            (StealTier::Common, false) => 55,
+            (StealTier::Common, true) => 80,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum StealOutcome {
+    Stolen(StealTier),
+    Failed,
+}
+
+impl StealOutcome {
+    /// How many RNG values the steal consumed
+    pub fn draws(&self) -> usize {
+        match *self {
+            StealOutcome::Stolen(StealTier::Rare) => 1,
+            StealOutcome::Stolen(StealTier::Uncommon) => 2,
+            StealOutcome::Stolen(StealTier::Common) | StealOutcome::Failed => 3,
+        }
+    }
+}
+
+/// The tiers in the order the game rolls for them, each roll taking one RNG value
+const ROLL_ORDER: [StealTier; 3] = [StealTier::Rare, StealTier::Uncommon, StealTier::Common];
+
+/// Works out the result of a steal from the upcoming RNG values
+///
+/// Returns `None` if there aren't enough values to decide the outcome
+pub fn steal(rng_vals: &[u32], thiefs_cuffs: bool) -> Option<StealOutcome> {
+    for (tier, rng_val) in ROLL_ORDER.iter().zip(rng_vals) {
+        if rng_val % 100 < tier.chance(thiefs_cuffs) {
+            return Some(StealOutcome::Stolen(*tier));
+        }
+    }
+    if rng_vals.len() >= ROLL_ORDER.len() {
+        Some(StealOutcome::Failed)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,49 @@
+use serde_derive::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// The release of the game being played.
+/// Only Vanilla's offensive spell powers differ; heal formulas, chest rolls and `rng::RNG::DEFAULT_SEED`
+/// are the same in every release. Each platform does seed the RNG its own way, see `seeding::SeedingModel`
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone, PartialEq)]
+pub enum GameVersion {
+    Vanilla,
+    IZJS,
+    #[default]
+    TZAConsole,
+    TZAPC,
+}
+
+impl GameVersion {
+    pub const ALL: [GameVersion; 4] = [
+        GameVersion::Vanilla,
+        GameVersion::IZJS,
+        GameVersion::TZAConsole,
+        GameVersion::TZAPC,
+    ];
+
+    pub fn name(&self) -> &str {
+        match *self {
+            GameVersion::Vanilla => "Vanilla",
+            GameVersion::IZJS => "IZJS",
+            GameVersion::TZAConsole => "TZA Console",
+            GameVersion::TZAPC => "TZA PC",
+        }
+    }
+
+    /// Whether the version uses the rebalanced spell powers introduced with IZJS
+    pub fn rebalanced(&self) -> bool {
+        *self != GameVersion::Vanilla
+    }
+}
+
+impl FromStr for GameVersion {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        GameVersion::ALL
+            .iter()
+            .find(|version| version.name() == s)
+            .copied()
+            .ok_or(())
+    }
+}
@@ -3,42 +3,90 @@ use std::str::FromStr;
 use log::*;
 use serde_derive::{Deserialize, Serialize};
 use yew::{html, Component, ComponentLink, Href, Html, InputData, Renderable, ShouldRender, Bridge, Bridged};
+use yew::format::{Json, Text};
+use yew::services::storage::{Area, StorageService};
 use yew::services::ConsoleService;
 use yew::virtual_dom::VNode;
 
 use ffxii_tza_rng::{character::Character, rng_helper::RNGHelper, rng::RNG, spell::Spell};
+use ffxii_tza_rng::action::Action;
+use ffxii_tza_rng::damage::WeaponType;
+use ffxii_tza_rng::enemy::Enemy;
+use ffxii_tza_rng::export;
+use ffxii_tza_rng::item::Item;
+use ffxii_tza_rng::party::Party;
+use ffxii_tza_rng::planner;
+use ffxii_tza_rng::search::SearchJob;
+use ffxii_tza_rng::session::Session;
+use ffxii_tza_rng::status::StatusAction;
+use ffxii_tza_rng::version::GameVersion;
 use ffxii_tza_rng::rng_helper::ValueLens;
 
 use crate::worker;
 
+/// Local storage key for the checkpoint of an unfinished seed search
+const CHECKPOINT_KEY: &str = "ffxii_tza_rng.search";
+/// Local storage key for the session, so a refresh doesn't lose the seed and position
+const SESSION_KEY: &str = "ffxii_tza_rng.session";
+
 #[derive(Default)]
 struct FFXIIApp {
     character: Character,
+    enemy: Enemy,
+    /// Party members other than `character`, who is always the first member
+    members: Vec<Character>,
     rng_helper: RNGHelper,
     cure_values: [Option<i32>; 5],
+    cure_members: [usize; 5],
     seed_min: Option<u32>,
     seed_max: Option<u32>,
     seed_iters: Option<usize>,
+    target: Option<u32>,
+    /// The way to `target`, worked out whenever the target or position changes
+    plan: String,
     finding: bool,
+    checkpoint: Option<SearchJob>,
+    /// A download of the results table, made when asked for
+    export: Option<(export::Format, String)>,
 }
 
 pub struct App {
     state: FFXIIApp,
     link: ComponentLink<Self>,
     console: ConsoleService,
+    storage: Option<StorageService>,
     worker: Box<dyn Bridge<worker::Worker>>,
 }
 
 pub enum Msg {
     StatChange(String, String),
+    SetVersion(String),
     SetSpell(String),
+    SetItem(String),
+    SetWeapon(String),
+    SetStatusAction(String),
+    SetAttack(String),
+    EnemyChange(String, String),
     SetCure(usize, String),
+    SetCureMember(usize, usize),
+    AddMember,
+    RemoveMember(usize),
     ToggleSerenity,
+    ToggleGenjiGloves,
+    ToggleFaith,
+    ToggleUndeadTarget,
+    ToggleBlind,
+    ToggleReverse,
     FindNext,
+    Advance(Action),
+    SetTarget(String),
     SeedChange(String),
     SeedParamChange(String, String),
     FindSeed,
+    ResumeSearch,
+    SearchProgress(SearchJob),
     FindResult(Option<RNGHelper>),
+    Export(export::Format),
 }
 
 impl Component for App {
@@ -46,23 +94,44 @@ impl Component for App {
     type Properties = ();
 
     fn create(_: Self::Properties, link: ComponentLink<Self>) -> Self {
-        let callback = link.callback(|helper| Msg::FindResult(helper));
+        let callback = link.callback(|response| match response {
+            worker::Response::Progress(job) => Msg::SearchProgress(job),
+            worker::Response::Done(helper) => Msg::FindResult(helper),
+        });
         let worker = worker::Worker::bridge(callback);
-        App {
+        let storage = StorageService::new(Area::Local).ok();
+        let checkpoint = storage.as_ref().and_then(|storage| {
+            let Json(job) = storage.restore(CHECKPOINT_KEY);
+            job.ok()
+        });
+        let session = storage.as_ref().and_then(|storage| {
+            let text: Text = storage.restore(SESSION_KEY);
+            text.ok().and_then(|json| Session::from_json(&json).ok())
+        });
+        let mut app = App {
             state: FFXIIApp {
-                seed_min: Some(5_500_000u32),
-                seed_max: Some(7_500_000u32),
+                seed_min: None,
+                seed_max: None,
                 seed_iters: Some(1000),
                 finding: false,
+                checkpoint,
                 ..Default::default()
             },
             link,
             console: ConsoleService::new(),
+            storage,
             worker,
+        };
+        if let Some(session) = session {
+            app.load_session(session);
         }
+        app.update_plan();
+        app
     }
 
     fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        // A prepared download is only good for the table it was made from
+        self.state.export = None;
         match msg {
             Msg::StatChange(attr, raw) => {
                 let mut value = match raw.parse::<u8>() {
@@ -75,17 +144,82 @@ impl Component for App {
                 match attr.as_str() {
                     "level" => self.state.character.level = value,
                     "magic" => self.state.character.magic = value,
+                    "strength" => self.state.character.strength = value,
+                    "vitality" => self.state.character.vitality = value,
+                    "speed" => self.state.character.speed = value,
+                    "magick_lore" => self.state.character.magick_lore = value,
+                    "targets" => self.state.character.targets = value,
+                    "potion_lore" => self.state.character.potion_lore = value,
                     _ => return false,
                 }
-                self.state.rng_helper.apply_character(&self.state.character);
+                self.apply_character();
             }
             Msg::ToggleSerenity => {
                 self.state.character.serenity = !self.state.character.serenity;
-                self.state.rng_helper.apply_character(&self.state.character);
+                self.apply_character();
+            }
+            Msg::ToggleGenjiGloves => {
+                self.state.character.genji_gloves = !self.state.character.genji_gloves;
+                self.apply_character();
+            }
+            Msg::ToggleFaith => {
+                self.state.character.faith = !self.state.character.faith;
+                self.apply_character();
+            }
+            Msg::ToggleUndeadTarget => {
+                self.state.character.undead_target = !self.state.character.undead_target;
+                self.apply_character();
+            }
+            Msg::ToggleBlind => {
+                self.state.character.blind = !self.state.character.blind;
+                self.apply_character();
+            }
+            Msg::ToggleReverse => {
+                self.state.enemy.reverse = !self.state.enemy.reverse;
+                self.state
+                    .rng_helper
+                    .apply_enemy(&self.state.character, &self.state.enemy);
+            }
+            Msg::SetVersion(version) => {
+                self.state.character.version =
+                    GameVersion::from_str(version.as_str()).unwrap_or_default();
+                self.apply_character();
             }
             Msg::SetSpell(spell) => {
                 self.state.character.spell = Spell::from_str(spell.as_str()).unwrap_or(Spell::Cure);
-                self.state.rng_helper.apply_character(&self.state.character);
+                self.apply_character();
+            }
+            Msg::SetItem(item) => {
+                self.state.character.item = Item::from_str(item.as_str()).ok();
+                self.apply_character();
+            }
+            Msg::SetWeapon(kind) => {
+                self.state.character.weapon.kind =
+                    WeaponType::from_str(kind.as_str()).unwrap_or(WeaponType::Unarmed);
+                self.apply_character();
+            }
+            Msg::SetStatusAction(action) => {
+                self.state.character.status_action = StatusAction::from_str(action.as_str()).ok();
+                self.apply_character();
+            }
+            Msg::SetAttack(raw) => {
+                self.state.character.weapon.attack = raw.parse::<u8>().unwrap_or(0);
+                self.apply_character();
+            }
+            Msg::EnemyChange(attr, raw) => {
+                let value = raw.parse::<u8>().unwrap_or(0);
+                match attr.as_str() {
+                    "defense" => self.state.enemy.defense = value,
+                    "magick_defense" => self.state.enemy.magick_defense = value,
+                    "status_resistance" => self.state.enemy.status_resistance = value,
+                    "shield" => self.state.enemy.evasion.shield = value,
+                    "parry" => self.state.enemy.evasion.parry = value,
+                    "evade" => self.state.enemy.evasion.evade = value,
+                    _ => return false,
+                }
+                self.state
+                    .rng_helper
+                    .apply_enemy(&self.state.character, &self.state.enemy);
             }
             Msg::SetCure(idx, val) => {
                 self.state.cure_values[idx] = match val.parse::<i32>() {
@@ -93,21 +227,59 @@ impl Component for App {
                     _ => None,
                 };
             }
+            Msg::SetCureMember(idx, member) => {
+                self.state.cure_members[idx] = member;
+            }
+            Msg::AddMember => {
+                if self.state.members.len() + 1 >= Party::MAX_MEMBERS {
+                    return false;
+                }
+                self.state.members.push(self.state.character);
+                self.state.rng_helper.apply_party(&self.party());
+            }
+            Msg::RemoveMember(idx) => {
+                if idx < self.state.members.len() {
+                    self.state.members.remove(idx);
+                }
+                self.state.rng_helper.apply_party(&self.party());
+            }
             Msg::FindNext => {
                 let values = &self.get_cure_values();
                 self.console.log(&"Searching for next pos");
-                if self
-                    .state
-                    .rng_helper
-                    .find_casts(&self.state.character, &values, None)
-                {
-                    return true;
+                let found = if self.uses_party(values.len()) {
+                    let casts = self.get_casts();
+                    self.state.rng_helper.apply_party(&self.party());
+                    self.state
+                        .rng_helper
+                        .find_party_casts(&self.state.character, &casts, None)
+                } else {
+                    self.state
+                        .rng_helper
+                        .find_casts(&self.state.character, &values, None)
+                };
+                if !found {
+                    return false;
                 }
-                return false;
+                self.update_plan();
+            }
+            Msg::Advance(action) => {
+                self.state
+                    .rng_helper
+                    .advance(&self.state.character, &[action]);
+                self.update_plan();
+            }
+            Msg::SetTarget(raw) => {
+                self.state.target = raw.parse::<u32>().ok();
+                self.update_plan();
             }
             Msg::SeedChange(val) => {
                 let seed = val.parse::<u32>().unwrap_or(RNG::DEFAULT_SEED);
                 self.state.rng_helper = RNGHelper::new(Some(seed), &self.state.character, 500);
+                self.state
+                    .rng_helper
+                    .apply_enemy(&self.state.character, &self.state.enemy);
+                self.state.rng_helper.apply_party(&self.party());
+                self.update_plan();
             }
             Msg::SeedParamChange(param, raw) => match param.as_str() {
                 "min" => {
@@ -147,8 +319,24 @@ impl Component for App {
                     Some(v) => *v,
                     None => 5_000,
                 };
+                if self.state.cure_members[..values.len()].iter().all(|member| *member == 0) {
+                    let job = SearchJob::new(
+                        &self.state.character,
+                        &self.state.enemy,
+                        &values,
+                        min,
+                        max,
+                        iters,
+                    );
+                    self.worker.send(worker::Request::Search(job));
+                    self.state.finding = true;
+                    return true;
+                }
                 self.worker.send(worker::Request::FindSeed(worker::Params {
                     character: self.state.character.clone(),
+                    enemy: self.state.enemy,
+                    party: self.party(),
+                    cure_members: self.state.cure_members[..values.len()].to_vec(),
                     cure_values: values,
                     min,
                     max,
@@ -169,10 +357,43 @@ impl Component for App {
                 //                    }
                 //                }
             }
+            Msg::ResumeSearch => {
+                let job = match &self.state.checkpoint {
+                    Some(job) => job.clone(),
+                    None => return false,
+                };
+                self.state.character = job.character;
+                self.state.enemy = job.enemy;
+                self.worker.send(worker::Request::Search(job));
+                self.state.finding = true;
+            }
+            Msg::SearchProgress(job) => {
+                if let Some(storage) = &mut self.storage {
+                    storage.store(CHECKPOINT_KEY, Json(&job));
+                }
+                self.state.checkpoint = Some(job);
+            }
+            Msg::Export(format) => {
+                let text = format.export(&self.state.rng_helper.values);
+                self.state.export = Some((format, data_url(format.mime_type(), &text)));
+                return true;
+            }
             Msg::FindResult(helper) => {
                 self.state.finding = false;
+                // A finished search has nothing left to resume
+                if let Some(storage) = &mut self.storage {
+                    storage.remove(CHECKPOINT_KEY);
+                }
+                self.state.checkpoint = None;
                 match helper {
-                    Some(h) => self.state.rng_helper = h,
+                    Some(h) => {
+                        self.state.rng_helper = h;
+                        self.state
+                            .rng_helper
+                            .apply_enemy(&self.state.character, &self.state.enemy);
+                        self.state.rng_helper.apply_party(&self.party());
+                        self.update_plan();
+                    }
                     None => {
                         self.console.log(&"No seed found :(");
                         return false;
@@ -180,6 +401,7 @@ impl Component for App {
                 }
             }
         }
+        self.save_session();
         true
     }
     
@@ -190,10 +412,13 @@ impl Component for App {
                 <div class="columns" style="height: 100%;">
                     <div class="column">
                         { self.view_character() }
+                        { self.view_party() }
                         { self.view_seed() }
                     </div>
                     <div class="column">
                         { self.view_cure() }
+                        { self.view_actions() }
+                        { self.view_enemy() }
                     </div>
                     <div class="column is-half" style="height: 100%;">
                         { self.view_results() }
@@ -207,6 +432,59 @@ impl Component for App {
 
 
 impl App {
+    fn load_session(&mut self, session: Session) {
+        self.state.character = session.character;
+        self.state.enemy = session.enemy;
+        self.state.members = session.party.members.iter().skip(1).copied().collect();
+        self.state.rng_helper = session.restore(500);
+        for (idx, (member, value)) in session.observations.iter().take(5).enumerate() {
+            self.state.cure_members[idx] = *member;
+            self.state.cure_values[idx] = Some(*value);
+        }
+        self.state.target = session.targets.first().copied();
+    }
+
+    fn save_session(&mut self) {
+        let targets: Vec<u32> = self.state.target.into_iter().collect();
+        let session = Session::capture(
+            &self.state.character,
+            &self.state.rng_helper,
+            &self.get_casts(),
+            &targets,
+        );
+        if let (Some(storage), Ok(json)) = (&mut self.storage, session.to_json()) {
+            let text: Text = Ok(json);
+            storage.store(SESSION_KEY, text);
+        }
+    }
+
+    /// Re-calcs the table for the edited character, keeping their place as the first party member
+    fn apply_character(&mut self) {
+        self.state.rng_helper.apply_character(&self.state.character);
+        self.state.rng_helper.apply_party(&self.party());
+    }
+
+    /// The whole party, with the character being edited as the first member
+    fn party(&self) -> Party {
+        let mut members = vec![self.state.character];
+        members.extend(self.state.members.iter().copied());
+        Party::new(members)
+    }
+
+    /// Whether any of the first `len` cures were cast by someone other than the first member
+    fn uses_party(&self, len: usize) -> bool {
+        self.state.cure_members[..len].iter().any(|member| *member != 0)
+    }
+
+    fn get_casts(&self) -> Vec<(usize, i32)> {
+        self.state
+            .cure_members
+            .iter()
+            .copied()
+            .zip(self.get_cure_values())
+            .collect()
+    }
+
     fn get_cure_values(&self) -> Vec<i32> {
         // First gather the cure values
         let mut values = vec![];
@@ -219,34 +497,181 @@ impl App {
         values
     }
 
+    fn version_opts(&self) -> Html {
+        let version = self.state.character.version;
+        html! {
+        <>
+            { for GameVersion::ALL.iter().map(|opt| {
+                let name = opt.name().to_string();
+                html! {
+                    <option onclick=self.link.callback(move |_| Msg::SetVersion(name.clone()))
+                            selected={*opt == version}>
+                        { opt.name() }
+                    </option>
+                }
+            }) }
+        </>
+        }
+    }
+
     fn spell_opts(&self) -> Html {
-        let spell = self.state.character.spell.name();
+        let spell = self.state.character.spell;
         html! {
         <>
-            <option onclick=self.link.callback(|_| Msg::SetSpell("Cure".to_string()))
-                    selected={spell == "Cure"}>
-                { "Cure" }
-            </option>
-            <option onclick=self.link.callback(|_| Msg::SetSpell("Cura".to_string()))
-                    selected={spell == "Cura"}>
-                { "Cura" }
-            </option>
-            <option onclick=self.link.callback(|_| Msg::SetSpell("Curaga".to_string()))
-                    selected={spell == "Curaga"}>
-                { "Curaga" }
+            { for Spell::ALL.iter().map(|opt| {
+                let name = opt.name().to_string();
+                html! {
+                    <option onclick=self.link.callback(move |_| Msg::SetSpell(name.clone()))
+                            selected={*opt == spell}>
+                        { opt.name() }
+                    </option>
+                }
+            }) }
+        </>
+        }
+    }
+
+    fn item_opts(&self) -> Html {
+        let item = self.state.character.item;
+        html! {
+        <>
+            <option onclick=self.link.callback(|_| Msg::SetItem("None".to_string()))
+                    selected={item.is_none()}>
+                { "None" }
             </option>
-            <option onclick=self.link.callback(|_| Msg::SetSpell("Curaja".to_string()))
-                    selected={spell == "Curaja"}>
-                { "Curaja" }
+            { for Item::ALL.iter().map(|opt| {
+                let name = opt.name().to_string();
+                html! {
+                    <option onclick=self.link.callback(move |_| Msg::SetItem(name.clone()))
+                            selected={item == Some(*opt)}>
+                        { opt.name() }
+                    </option>
+                }
+            }) }
+        </>
+        }
+    }
+
+    fn status_opts(&self) -> Html {
+        let action = self.state.character.status_action;
+        html! {
+        <>
+            <option onclick=self.link.callback(|_| Msg::SetStatusAction("None".to_string()))
+                    selected={action.is_none()}>
+                { "None" }
             </option>
+            { for StatusAction::ALL.iter().map(|opt| {
+                let name = opt.name().to_string();
+                html! {
+                    <option onclick=self.link.callback(move |_| Msg::SetStatusAction(name.clone()))
+                            selected={action == Some(*opt)}>
+                        { opt.name() }
+                    </option>
+                }
+            }) }
         </>
         }
     }
 
+    fn weapon_opts(&self) -> Html {
+        let weapon = self.state.character.weapon.kind;
+        html! {
+        <>
+            { for WeaponType::ALL.iter().map(|kind| {
+                let name = kind.name().to_string();
+                html! {
+                    <option onclick=self.link.callback(move |_| Msg::SetWeapon(name.clone()))
+                            selected={*kind == weapon}>
+                        { kind.name() }
+                    </option>
+                }
+            }) }
+        </>
+        }
+    }
+
+    fn number_field(&self, label: &str, value: u8, msg: fn(String) -> Msg) -> Html {
+        html! {
+        <div class="field is-horizontal">
+            <div class="field-label is-normal">
+                <label class="label">{label}</label>
+            </div>
+            <div class="field-body">
+                <div class="field">
+                    <div class="control is-expanded">
+                        <input class="input"
+                               oninput=self.link.callback(move |val: InputData| msg(val.value))
+                               value=&value>
+                        </input>
+                    </div>
+                </div>
+            </div>
+        </div>
+        }
+    }
+
+    fn checkbox_field(&self, label: &str, checked: bool, msg: fn() -> Msg) -> Html {
+        html! {
+        <div class="field is-horizontal">
+            <div class="field-label">
+                <label class="label is-normal">{label}</label>
+            </div>
+            <div class="field-body">
+                <div class="field" style="display: flex; align-items: center;">
+                    <div class="control" style="display: flex; align-items: center;">
+                        <input checked=checked
+                               onclick=self.link.callback(move |_| msg())
+                               type="checkbox">
+                        </input>
+                    </div>
+                </div>
+            </div>
+        </div>
+        }
+    }
+
+    fn view_enemy(&self) -> Html {
+        html! {
+        <div class="box">
+            <h2 class="subtitle has-text-centered">{"Enemy"}</h2>
+            { self.number_field("Defense", self.state.enemy.defense,
+                |val| Msg::EnemyChange("defense".to_string(), val)) }
+            { self.number_field("Magick Def", self.state.enemy.magick_defense,
+                |val| Msg::EnemyChange("magick_defense".to_string(), val)) }
+            { self.number_field("Status Resist", self.state.enemy.status_resistance,
+                |val| Msg::EnemyChange("status_resistance".to_string(), val)) }
+            { self.number_field("Shield", self.state.enemy.evasion.shield,
+                |val| Msg::EnemyChange("shield".to_string(), val)) }
+            { self.number_field("Parry", self.state.enemy.evasion.parry,
+                |val| Msg::EnemyChange("parry".to_string(), val)) }
+            { self.number_field("Evade", self.state.enemy.evasion.evade,
+                |val| Msg::EnemyChange("evade".to_string(), val)) }
+            { self.checkbox_field("Reverse", self.state.enemy.reverse, || Msg::ToggleReverse) }
+        </div>
+        }
+    }
+
     fn view_character(&self) -> Html {
         html! {
         <div class="box">
             <h2 class="subtitle has-text-centered">{"Character"}</h2>
+            <div class="field is-horizontal">
+                <div class="field-label is-normal">
+                    <label class="label">{"Version"}</label>
+                </div>
+                <div class="field-body">
+                    <div class="field">
+                        <div class="control is-expanded">
+                            <div class="select is-fullwidth">
+                              <select>
+                                { self.version_opts() }
+                              </select>
+                            </div>
+                        </div>
+                        <p class="help">{"Sets Vanilla's spell powers and where seed searches start"}</p>
+                    </div>
+                </div>
+            </div>
             <div class="field is-horizontal">
                 <div class="field-label is-normal">
                     <label class="label">{"Level"}</label>
@@ -297,6 +722,45 @@ impl App {
                     </div>
                 </div>
             </div>
+            { self.number_field("Strength", self.state.character.strength,
+                |val| Msg::StatChange("strength".to_string(), val)) }
+            { self.number_field("Vitality", self.state.character.vitality,
+                |val| Msg::StatChange("vitality".to_string(), val)) }
+            { self.number_field("Speed", self.state.character.speed,
+                |val| Msg::StatChange("speed".to_string(), val)) }
+            <div class="field is-horizontal">
+                <div class="field-label is-normal">
+                    <label class="label">{"Weapon"}</label>
+                </div>
+                <div class="field-body">
+                    <div class="field">
+                        <div class="control is-expanded">
+                            <div class="select is-fullwidth">
+                              <select>
+                                { self.weapon_opts() }
+                              </select>
+                            </div>
+                        </div>
+                    </div>
+                </div>
+            </div>
+            { self.number_field("Attack", self.state.character.weapon.attack, Msg::SetAttack) }
+            <div class="field is-horizontal">
+                <div class="field-label is-normal">
+                    <label class="label">{"Status"}</label>
+                </div>
+                <div class="field-body">
+                    <div class="field">
+                        <div class="control is-expanded">
+                            <div class="select is-fullwidth">
+                              <select>
+                                { self.status_opts() }
+                              </select>
+                            </div>
+                        </div>
+                    </div>
+                </div>
+            </div>
             <div class="field is-horizontal">
                 <div class="field-label">
                     <label class="label is-normal">{"Serenity"}</label>
@@ -312,6 +776,34 @@ impl App {
                     </div>
                 </div>
             </div>
+            <div class="field is-horizontal">
+                <div class="field-label is-normal">
+                    <label class="label">{"Item"}</label>
+                </div>
+                <div class="field-body">
+                    <div class="field">
+                        <div class="control is-expanded">
+                            <div class="select is-fullwidth">
+                              <select>
+                                { self.item_opts() }
+                              </select>
+                            </div>
+                        </div>
+                    </div>
+                </div>
+            </div>
+            { self.number_field("Potion Lore", self.state.character.potion_lore,
+                |val| Msg::StatChange("potion_lore".to_string(), val)) }
+            { self.checkbox_field("Faith", self.state.character.faith, || Msg::ToggleFaith) }
+            { self.number_field("Magick Lore", self.state.character.magick_lore,
+                |val| Msg::StatChange("magick_lore".to_string(), val)) }
+            { self.number_field("Targets", self.state.character.targets,
+                |val| Msg::StatChange("targets".to_string(), val)) }
+            { self.checkbox_field("Undead Target", self.state.character.undead_target,
+                || Msg::ToggleUndeadTarget) }
+            { self.checkbox_field("Genji Gloves", self.state.character.genji_gloves,
+                || Msg::ToggleGenjiGloves) }
+            { self.checkbox_field("Blind", self.state.character.blind, || Msg::ToggleBlind) }
         </div>
         }
     }
@@ -321,17 +813,39 @@ impl App {
         <div class="control">
             {
                 if self.state.finding {
+                    let progress = match &self.state.checkpoint {
+                        Some(job) => format!(
+                            " {} of {}",
+                            job.searched(),
+                            job.searched() + job.remaining()
+                        ),
+                        None => "".to_string(),
+                    };
                     html! {
                         <button class="button is-error">
-                            {"Searching..."}
+                            { format!("Searching...{}", progress) }
                         </button>
                     }
                 } else {
                     html! {
+                    <>
                         <button onclick=self.link.callback(|_| Msg::FindSeed)
                                 class="button is-primary">
                             {"Search"}
                         </button>
+                        {
+                            if self.state.checkpoint.is_some() {
+                                html! {
+                                    <button onclick=self.link.callback(|_| Msg::ResumeSearch)
+                                            class="button">
+                                        {"Resume"}
+                                    </button>
+                                }
+                            } else {
+                                html! {}
+                            }
+                        }
+                    </>
                     }
                 }
             }
@@ -365,6 +879,7 @@ impl App {
                                    oninput=self.link.callback(
                                         |val: InputData| Msg::SeedParamChange("min".to_string(),val.value)
                                     )
+                                    placeholder="Any"
                                     value=match self.state.seed_min {
                                         Some(v) => v.to_string(),
                                         None => "".to_string()
@@ -386,6 +901,7 @@ impl App {
                                    oninput=self.link.callback(
                                         |val: InputData| Msg::SeedParamChange("max".to_string(),val.value)
                                     )
+                                   placeholder="Any"
                                    value=match self.state.seed_max {
                                         Some(v) => v.to_string(),
                                         None => "".to_string()
@@ -421,9 +937,33 @@ impl App {
         }
     }
 
+    fn cure_member_opts(&self, idx: usize) -> Html {
+        let selected = self.state.cure_members[idx];
+        html! {
+        <>
+            { for (0..=self.state.members.len()).map(|member| {
+                html! {
+                    <option onclick=self.link.callback(move |_| Msg::SetCureMember(idx, member))
+                            selected={member == selected}>
+                        { format!("Member {}", member + 1) }
+                    </option>
+                }
+            }) }
+        </>
+        }
+    }
+
     fn cure_input(&self, idx: usize) -> Html {
         html! {
-        <div class="field">
+        <div class="field has-addons">
+            <div class="control">
+                <div class="select">
+                    <select>
+                        { self.cure_member_opts(idx) }
+                    </select>
+                </div>
+            </div>
+            <div class="control is-expanded">
             {
             match self.state.cure_values[idx] {
                 Some(v) => {
@@ -440,6 +980,37 @@ impl App {
                 }
             }
             }
+            </div>
+        </div>
+        }
+    }
+
+    fn view_party(&self) -> Html {
+        html! {
+        <div class="box">
+            <h2 class="subtitle has-text-centered">{"Party"}</h2>
+            <p>{ format!("Member 1: Lv {} Mag {} {}", self.state.character.level,
+                self.state.character.magic, self.state.character.spell.name()) }</p>
+            { for self.state.members.iter().enumerate().map(|(idx, member)| {
+                html! {
+                    <div class="field has-addons">
+                        <p class="control is-expanded">
+                            { format!("Member {}: Lv {} Mag {} {}", idx + 2, member.level,
+                                member.magic, member.spell.name()) }
+                        </p>
+                        <div class="control">
+                            <button class="button is-small"
+                                    onclick=self.link.callback(move |_| Msg::RemoveMember(idx))>
+                                {"Remove"}
+                            </button>
+                        </div>
+                    </div>
+                }
+            }) }
+            <button class="button" style="width: 100%;"
+                    onclick=self.link.callback(|_| Msg::AddMember)>
+                {"Add current character"}
+            </button>
         </div>
         }
     }
@@ -455,17 +1026,86 @@ impl App {
         }
     }
 
+    fn view_actions(&self) -> Html {
+        html! {
+        <div class="box">
+            <h2 class="subtitle has-text-centered">{"Actions"}</h2>
+            <div class="buttons">
+                { for Action::TABLE.iter().filter(|action| action.draws() > 0).map(|action| {
+                    let action = *action;
+                    html! {
+                        <button class="button"
+                                onclick=self.link.callback(move |_| Msg::Advance(action))>
+                            { action.to_string() }
+                        </button>
+                    }
+                }) }
+            </div>
+            <div class="field is-horizontal">
+                <div class="field-label is-normal">
+                    <label class="label">{"Target"}</label>
+                </div>
+                <div class="field-body">
+                    <div class="field">
+                        <div class="control is-expanded">
+                            <input class="input"
+                                   oninput=self.link.callback(|val: InputData| Msg::SetTarget(val.value))
+                                   value=match self.state.target {
+                                        Some(v) => v.to_string(),
+                                        None => "".to_string()
+                                   }>
+                            </input>
+                        </div>
+                    </div>
+                </div>
+            </div>
+            <p>{ &self.state.plan }</p>
+        </div>
+        }
+    }
+
+    fn update_plan(&mut self) {
+        self.state.plan = match self.state.target {
+            Some(target) => match self.state.rng_helper.plan_to(target, &Action::TABLE) {
+                Some(steps) => planner::describe(&steps),
+                None => "Can't reach that position".to_string(),
+            },
+            None => "".to_string(),
+        };
+    }
+
     fn view_results(&self) -> Html {
         html! {
         <div class="box" style="height: 100%; display: flex; flex-direction: column; max-height: 100%;">
             <h2 class="subtitle has-text-centered">{"Result"}</h2>
+            <div class="buttons is-centered">
+                { for export::Format::ALL.iter().map(|format| {
+                    let format = *format;
+                    html! {
+                        <button class="button is-small"
+                                onclick=self.link.callback(move |_| Msg::Export(format))>
+                            { format!("Export {}", format.name()) }
+                        </button>
+                    }
+                }) }
+                { self.export_link() }
+            </div>
             <div style="display: block; flex: 1; width: 100%; max-height: 100%; overflow: auto;">
                 <table class="table is-hoverable is-fullwidth" style="height: 100%; width: 100%;">
                     <thead style="position: sticky; top: 0; background: white;">
                     <tr>
                         <th>{"Pos"}</th>
                         <th>{"Value"}</th>
-                        <th>{"Cure"}</th>
+                        <th>{"Spell"}</th>
+                        { for (0..self.state.members.len()).map(|idx| html! {
+                            <th>{ format!("Member {}", idx + 2) }</th>
+                        }) }
+                        <th>{"Attack"}</th>
+                        <th>{"Crit"}</th>
+                        <th>{"Combo"}</th>
+                        <th>{"Status"}</th>
+                        <th>{"Hit"}</th>
+                        <th>{"Defend"}</th>
                         <th>{"Chance"}</th>
                     </tr>
                     </thead>
@@ -478,14 +1118,49 @@ impl App {
         }
     }
 
+    /// A link downloading the last export, if there is one
+    fn export_link(&self) -> Html {
+        match &self.state.export {
+            Some((format, url)) => html! {
+                <a class="button is-small is-primary"
+                   href=url
+                   download=format!("ffxii-rng.{}", format.extension())>
+                    { format!("Download {}", format.name()) }
+                </a>
+            },
+            None => html! {},
+        }
+    }
+
     fn result_row(&self, value: &ValueLens) -> Html {
         html! {
         <tr>
             <td>{value.position}</td>
             <td>{value.value}</td>
             <td>{value.spell}</td>
+            { for value.party.iter().skip(1).map(|heal| html! { <td>{heal}</td> }) }
+            <td>{value.attack}</td>
+            <td>{ if value.crit { "Crit" } else { "" } }</td>
+            <td>{ if value.combo { "Combo" } else { "" } }</td>
+            <td>{ if value.status { "Hit" } else { "" } }</td>
+            <td>{ value.hit.name() }</td>
+            <td>{ value.defend.name() }</td>
             <td>{value.chest}</td>
         </tr>
         }
     }
 }
+
+/// A `data:` URL holding `text`, for download links
+fn data_url(mime_type: &str, text: &str) -> String {
+    let mut url = format!("data:{};charset=utf-8,", mime_type);
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                url.push(byte as char)
+            }
+            _ => url.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    url
+}
@@ -8,11 +8,18 @@ use yew::services::Task;
 use yew::worker::*;
 
 use ffxii_tza_rng::character;
+use ffxii_tza_rng::enemy;
+use ffxii_tza_rng::party;
 use ffxii_tza_rng::rng_helper;
+use ffxii_tza_rng::search::SearchJob;
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Params {
     pub character: character::Character,
+    pub enemy: enemy::Enemy,
+    pub party: party::Party,
+    /// Which party member cast each of `cure_values`
+    pub cure_members: Vec<usize>,
     pub cure_values: Vec<i32>,
     pub min: u32,
     pub max: u32,
@@ -22,6 +29,15 @@ pub struct Params {
 #[derive(Serialize, Deserialize, Debug)]
 pub enum Request {
     FindSeed(Params),
+    /// Start or resume a checkpointed search
+    Search(SearchJob),
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Response {
+    /// The state of a search after another chunk, to be saved as a checkpoint
+    Progress(SearchJob),
+    Done(Option<rng_helper::RNGHelper>),
 }
 
 
@@ -38,7 +54,7 @@ impl Agent for Worker {
     type Reach = Public;
     type Message = Msg;
     type Input = Request;
-    type Output = Option<rng_helper::RNGHelper>;
+    type Output = Response;
 
     fn create(link: AgentLink<Self>) -> Self {
         Worker {
@@ -54,17 +70,44 @@ impl Agent for Worker {
         info!("Request: {:?}", msg);
         match msg {
             Request::FindSeed(params) => {
+                if params.cure_members.iter().any(|member| *member != 0) {
+                    let casts: Vec<(usize, i32)> = params
+                        .cure_members
+                        .iter()
+                        .copied()
+                        .zip(params.cure_values.iter().copied())
+                        .collect();
+                    self.link.respond(
+                        who,
+                        Response::Done(rng_helper::RNGHelper::find_seed_for_party(
+                            &params.character,
+                            &params.party,
+                            &params.enemy,
+                            &casts,
+                            params.min,
+                            params.max,
+                            params.iters,
+                        )),
+                    );
+                    return;
+                }
                 self.link.respond(
                     who,
-                    rng_helper::RNGHelper::find_seed(
+                    Response::Done(rng_helper::RNGHelper::find_seed(
                         &params.character,
+                        &params.enemy,
                         &params.cure_values,
                         params.min,
                         params.max,
                         params.iters,
-                    ),
+                    )),
                 );
             }
+            Request::Search(mut job) => {
+                let link = &self.link;
+                let found = job.run(|job| link.respond(who, Response::Progress(job.clone())));
+                self.link.respond(who, Response::Done(found));
+            }
         }
     }
 